@@ -0,0 +1,99 @@
+use crate::{
+    combinatorics::binomial,
+    error::{FixedFastError, Result},
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+};
+
+/// Probability of exactly `k` successes in `n` trials with success
+/// probability `p`, `C(n, k) p^k (1-p)^(n-k)`. Fails with `Overflow` when
+/// `C(n, k)` does not fit `T`, which limits `n` to about 100 at F9.
+pub fn binomial_pmf<T: FixedPrecision>(
+    n: u32,
+    k: u32,
+    p: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    validate(n, k, p)?;
+    let q = FixedDecimal::<T>::one() - p;
+    binomial::<T>(n, k)?
+        .checked_mul(p.pow_i128(k as i128))?
+        .checked_mul(q.pow_i128((n - k) as i128))
+}
+
+/// Probability of at most `k` successes in `n` trials, summing the mass
+/// function term by term. Truncation can leave the total a few ulps short
+/// of one for `k == n`.
+pub fn binomial_cdf<T: FixedPrecision>(
+    n: u32,
+    k: u32,
+    p: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    validate(n, k, p)?;
+    (0..=k).try_fold(FixedDecimal::zero(), |sum, i| {
+        sum.checked_add(binomial_pmf(n, i, p)?)
+    })
+}
+
+fn validate<T: FixedPrecision>(n: u32, k: u32, p: FixedDecimal<T>) -> Result<()> {
+    if !(FixedDecimal::zero()..=FixedDecimal::one()).contains(&p) {
+        return Err(FixedFastError::DomainError(
+            "binomial probability must lie in [0, 1]",
+        ));
+    }
+    if k > n {
+        return Err(FixedFastError::DomainError(
+            "binomial successes cannot exceed trials",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    fn d(s: &str) -> FixedDecimal<F9> {
+        FixedDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_binomial_pmf() {
+        let p = d("0.3");
+        let expected = [
+            "0.16807", "0.36015", "0.3087", "0.1323", "0.02835", "0.00243",
+        ];
+        for (k, expected) in expected.iter().enumerate() {
+            assert_eq!(binomial_pmf(5, k as u32, p).unwrap(), d(expected));
+        }
+        assert_eq!(binomial_pmf(10, 5, d("0.5")).unwrap(), d("0.24609375"));
+        assert_eq!(binomial_pmf(4, 0, d("0")).unwrap(), FixedDecimal::one());
+        assert_eq!(binomial_pmf(4, 4, d("1")).unwrap(), FixedDecimal::one());
+        assert!(matches!(
+            binomial_pmf(5, 6, p),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(matches!(
+            binomial_pmf(5, 2, d("1.5")),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(binomial_pmf(5, 2, d("-0.1")).is_err());
+    }
+
+    #[test]
+    fn test_binomial_cdf() {
+        let p = d("0.3");
+        assert_eq!(binomial_cdf(5, 0, p).unwrap(), d("0.16807"));
+        assert_eq!(binomial_cdf(5, 2, p).unwrap(), d("0.83692"));
+        assert_eq!(binomial_cdf(5, 5, p).unwrap(), FixedDecimal::one());
+        // 0.5^10 is truncated in its last digit at F9
+        let cdf = binomial_cdf(10, 5, d("0.5")).unwrap();
+        assert!((cdf - d("0.623046875")).abs() <= d("0.00000001"));
+        assert!(binomial_cdf(5, 6, p).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+use std::marker::PhantomData;
+
+use crate::{
+    cdf::CDFCustomAprox,
+    error::{FixedFastError, Result},
+    exp::range_reduce_taylor_exp,
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    function::TryFunction,
+    ln::range_reduce_arctanh_ln_try,
+    pdf::PDF,
+    solver::bisection,
+    sqrt::sqrt_newton_raphson_try,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+/// European option pricer. The normal CDF and PDF are pluggable so callers
+/// can swap the analytic approximations for lookup tables such as `CDFV1`
+/// and `PDFV1`.
+pub struct BlackScholes<
+    T: FixedPrecision,
+    C: TryFunction<T> = CDFCustomAprox<T>,
+    P: TryFunction<T> = PDF<T>,
+> {
+    cdf: C,
+    pdf: P,
+    _precision: PhantomData<T>,
+}
+
+impl<T: FixedPrecision> BlackScholes<T> {
+    pub fn new() -> Self {
+        Self::with_cdf(CDFCustomAprox::new())
+    }
+}
+
+impl<T: FixedPrecision, C: TryFunction<T>> BlackScholes<T, C> {
+    pub fn with_cdf(cdf: C) -> Self {
+        Self::with_functions(cdf, PDF::new())
+    }
+}
+
+impl<T: FixedPrecision> Default for BlackScholes<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FixedPrecision, C: TryFunction<T>, P: TryFunction<T>> BlackScholes<T, C, P> {
+    pub fn with_functions(cdf: C, pdf: P) -> Self {
+        Self {
+            cdf,
+            pdf,
+            _precision: PhantomData,
+        }
+    }
+
+    pub fn d1_d2(
+        &self,
+        spot: FixedDecimal<T>,
+        strike: FixedDecimal<T>,
+        rate: FixedDecimal<T>,
+        volatility: FixedDecimal<T>,
+        time: FixedDecimal<T>,
+    ) -> Result<(FixedDecimal<T>, FixedDecimal<T>)> {
+        validate(spot, strike, volatility, time)?;
+        let vol_sqrt_time = volatility * sqrt_newton_raphson_try::<T, 20>(time)?;
+        let log_moneyness = range_reduce_arctanh_ln_try::<T, 20>(spot.checked_div(strike)?)?;
+        let drift: FixedDecimal<T> = (rate + volatility.squared() / 2) * time;
+        let d1 = drift.add(log_moneyness).checked_div(vol_sqrt_time)?;
+        Ok((d1, d1 - vol_sqrt_time))
+    }
+
+    /// Prices a European option. A zero volatility or zero time to expiry
+    /// collapses to the discounted intrinsic value.
+    pub fn price(
+        &self,
+        kind: OptionKind,
+        spot: FixedDecimal<T>,
+        strike: FixedDecimal<T>,
+        rate: FixedDecimal<T>,
+        volatility: FixedDecimal<T>,
+        time: FixedDecimal<T>,
+    ) -> Result<FixedDecimal<T>> {
+        validate(spot, strike, volatility, time)?;
+        let discounted_strike = strike * range_reduce_taylor_exp::<T, 20>(-rate * time);
+        if volatility == 0 || time == 0 {
+            let intrinsic = match kind {
+                OptionKind::Call => spot - discounted_strike,
+                OptionKind::Put => discounted_strike - spot,
+            };
+            return Ok(intrinsic.max(FixedDecimal::zero()));
+        }
+        let (d1, d2) = self.d1_d2(spot, strike, rate, volatility, time)?;
+        match kind {
+            OptionKind::Call => Ok(spot * self.cdf.try_evaluate(d1)?
+                - discounted_strike * self.cdf.try_evaluate(d2)?),
+            OptionKind::Put => Ok(discounted_strike * self.cdf.try_evaluate(-d2)?
+                - spot * self.cdf.try_evaluate(-d1)?),
+        }
+    }
+
+    /// Backs out the volatility in `[0, 5]` that reproduces `price`, returning
+    /// a `DomainError` when the price is not attainable within that bracket.
+    pub fn implied_vol(
+        &self,
+        kind: OptionKind,
+        price: FixedDecimal<T>,
+        spot: FixedDecimal<T>,
+        strike: FixedDecimal<T>,
+        rate: FixedDecimal<T>,
+        time: FixedDecimal<T>,
+    ) -> Result<FixedDecimal<T>> {
+        let lo = FixedDecimal::zero();
+        let hi = FixedDecimal::from_i128(5);
+        let price_at = |volatility| self.price(kind, spot, strike, rate, volatility, time);
+        if price < price_at(lo)? || price > price_at(hi)? {
+            return Err(FixedFastError::DomainError(
+                "no implied volatility in [0, 5] for this price",
+            ));
+        }
+        bisection(
+            |volatility| Ok(price_at(volatility)? - price),
+            lo,
+            hi,
+            FixedDecimal::min_positive(),
+            128,
+        )
+    }
+
+    /// Sensitivity of the price to the spot: `N(d1)` for a call and
+    /// `N(d1) - 1` for a put.
+    pub fn delta(
+        &self,
+        kind: OptionKind,
+        spot: FixedDecimal<T>,
+        strike: FixedDecimal<T>,
+        rate: FixedDecimal<T>,
+        volatility: FixedDecimal<T>,
+        time: FixedDecimal<T>,
+    ) -> Result<FixedDecimal<T>> {
+        let (d1, _) = self.d1_d2(spot, strike, rate, volatility, time)?;
+        let call_delta = self.cdf.try_evaluate(d1)?;
+        match kind {
+            OptionKind::Call => Ok(call_delta),
+            OptionKind::Put => Ok(call_delta - 1),
+        }
+    }
+
+    /// Second derivative of the price with respect to the spot,
+    /// `pdf(d1) / (S * sigma * sqrt(T))`. Identical for calls and puts.
+    pub fn gamma(
+        &self,
+        spot: FixedDecimal<T>,
+        strike: FixedDecimal<T>,
+        rate: FixedDecimal<T>,
+        volatility: FixedDecimal<T>,
+        time: FixedDecimal<T>,
+    ) -> Result<FixedDecimal<T>> {
+        let (d1, _) = self.d1_d2(spot, strike, rate, volatility, time)?;
+        let denominator = spot * volatility * sqrt_newton_raphson_try::<T, 20>(time)?;
+        self.pdf.try_evaluate(d1)?.checked_div(denominator)
+    }
+
+    /// Sensitivity of the price to the volatility, `S * pdf(d1) * sqrt(T)`.
+    /// Identical for calls and puts.
+    pub fn vega(
+        &self,
+        spot: FixedDecimal<T>,
+        strike: FixedDecimal<T>,
+        rate: FixedDecimal<T>,
+        volatility: FixedDecimal<T>,
+        time: FixedDecimal<T>,
+    ) -> Result<FixedDecimal<T>> {
+        let (d1, _) = self.d1_d2(spot, strike, rate, volatility, time)?;
+        Ok(spot * self.pdf.try_evaluate(d1)? * sqrt_newton_raphson_try::<T, 20>(time)?)
+    }
+}
+
+fn validate<T: FixedPrecision>(
+    spot: FixedDecimal<T>,
+    strike: FixedDecimal<T>,
+    volatility: FixedDecimal<T>,
+    time: FixedDecimal<T>,
+) -> Result<()> {
+    if spot <= 0 || strike <= 0 {
+        return Err(FixedFastError::DomainError(
+            "spot and strike must be positive",
+        ));
+    }
+    if volatility < 0 || time < 0 {
+        return Err(FixedFastError::DomainError(
+            "volatility and time must be non-negative",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    fn d(s: &str) -> FixedDecimal<F9> {
+        FixedDecimal::<F9>::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_price() {
+        let bs = BlackScholes::<F9>::new();
+        let call = bs
+            .price(
+                OptionKind::Call,
+                d("100"),
+                d("100"),
+                d("0.05"),
+                d("0.2"),
+                d("1"),
+            )
+            .unwrap();
+        assert!((call - d("10.450583572")).abs() < d("0.0001"));
+        let put = bs
+            .price(
+                OptionKind::Put,
+                d("100"),
+                d("100"),
+                d("0.05"),
+                d("0.2"),
+                d("1"),
+            )
+            .unwrap();
+        assert!((put - d("5.573526022")).abs() < d("0.0001"));
+    }
+
+    #[test]
+    fn test_implied_vol() {
+        let bs = BlackScholes::<F9>::new();
+        for (kind, vol) in [
+            (OptionKind::Call, d("0.2")),
+            (OptionKind::Put, d("0.35")),
+            (OptionKind::Call, d("1.5")),
+        ] {
+            let price = bs
+                .price(kind, d("100"), d("90"), d("0.03"), vol, d("0.5"))
+                .unwrap();
+            let implied = bs
+                .implied_vol(kind, price, d("100"), d("90"), d("0.03"), d("0.5"))
+                .unwrap();
+            assert!((implied - vol).abs() < d("0.000001"));
+        }
+        // a call can never be worth more than the underlying
+        assert!(
+            bs.implied_vol(
+                OptionKind::Call,
+                d("150"),
+                d("100"),
+                d("90"),
+                d("0.03"),
+                d("0.5")
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_greeks_match_numerical_derivatives() {
+        let bs = BlackScholes::<F9>::new();
+        let (spot, strike, rate, vol, time) = (d("100"), d("95"), d("0.04"), d("0.25"), d("0.75"));
+        let call = |s, v| {
+            bs.price(OptionKind::Call, s, strike, rate, v, time)
+                .unwrap()
+        };
+        let put = |s, v| bs.price(OptionKind::Put, s, strike, rate, v, time).unwrap();
+
+        let h = d("0.01");
+        let numerical_delta: FixedDecimal<F9> =
+            (call(spot + h, vol) - call(spot - h, vol)) / (h * 2);
+        let delta = bs
+            .delta(OptionKind::Call, spot, strike, rate, vol, time)
+            .unwrap();
+        assert!((delta - numerical_delta).abs() < d("0.001"));
+        let numerical_delta: FixedDecimal<F9> = (put(spot + h, vol) - put(spot - h, vol)) / (h * 2);
+        let delta = bs
+            .delta(OptionKind::Put, spot, strike, rate, vol, time)
+            .unwrap();
+        assert!((delta - numerical_delta).abs() < d("0.001"));
+
+        let h = d("1");
+        let numerical_gamma: FixedDecimal<F9> =
+            (call(spot + h, vol) - call(spot, vol) * 2 + call(spot - h, vol)) / h.squared();
+        let gamma = bs.gamma(spot, strike, rate, vol, time).unwrap();
+        assert!((gamma - numerical_gamma).abs() < d("0.0001"));
+
+        let h = d("0.001");
+        let numerical_vega: FixedDecimal<F9> =
+            (call(spot, vol + h) - call(spot, vol - h)) / (h * 2);
+        let vega = bs.vega(spot, strike, rate, vol, time).unwrap();
+        assert!((vega - numerical_vega).abs() < d("0.01"));
+    }
+}
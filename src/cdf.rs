@@ -2,12 +2,16 @@ use std::marker::PhantomData;
 
 use crate::{
     FixedDecimal,
-    error::Result,
+    erf::erfc,
+    error::{FixedFastError, Result},
     exp::range_reduce_taylor_exp,
     fixed_decimal::FixedPrecision,
-    function::{Function, TryFunction},
-    interpolation::linear_interpolation,
-    lookup_table::LookupTable,
+    function::Function,
+    interpolation::{linear_interpolation, linear_interpolation_with_slope},
+    ln::range_reduce_arctanh_ln_try,
+    lookup_table::{Interpolation, LookupTable, LookupTableBuilder},
+    pdf::pdf,
+    sqrt::sqrt_newton_raphson_try,
 };
 
 pub type CDFV1<T> = CDFLinearInterpLookupTable<T>;
@@ -18,43 +22,92 @@ pub struct CDFCustomAprox<T: FixedPrecision> {
 }
 
 impl<T: FixedPrecision> CDFCustomAprox<T> {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
             _precision: PhantomData,
             coefficients: [
-                FixedDecimal::from_str("-0.00000000436953479").unwrap(), //0
-                FixedDecimal::from_str("1.59576962000000000").unwrap(),  //1
-                FixedDecimal::from_str("-0.00000955404601").unwrap(),    //2
-                FixedDecimal::from_str("0.07274169990000000").unwrap(),  //3
-                FixedDecimal::from_str("-0.00026565023900000").unwrap(), //4
-                FixedDecimal::from_str("0.00050857094000000").unwrap(),  //5
-                FixedDecimal::from_str("-0.00079653385500000").unwrap(), //6
-                FixedDecimal::from_str("0.00059778488700000").unwrap(),  //7
-                FixedDecimal::from_str("-0.00040077230600000").unwrap(), //8
-                FixedDecimal::from_str("0.00014965658000000").unwrap(),  //9
-                FixedDecimal::from_str("-0.00002988796070000").unwrap(), //10
-                FixedDecimal::from_str("0.00000306494352000").unwrap(),  //11
-                FixedDecimal::from_str("-0.00000012783404900").unwrap(), //12
+                FixedDecimal::from_scaled(0, -436953479, 17), //0
+                FixedDecimal::from_scaled(1, 59576962, 8),    //1
+                FixedDecimal::from_scaled(0, -955404601, 14), //2
+                FixedDecimal::from_scaled(0, 727416999, 10),  //3
+                FixedDecimal::from_scaled(0, -265650239, 12), //4
+                FixedDecimal::from_scaled(0, 50857094, 11),   //5
+                FixedDecimal::from_scaled(0, -796533855, 12), //6
+                FixedDecimal::from_scaled(0, 597784887, 12),  //7
+                FixedDecimal::from_scaled(0, -400772306, 12), //8
+                FixedDecimal::from_scaled(0, 14965658, 11),   //9
+                FixedDecimal::from_scaled(0, -298879607, 13), //10
+                FixedDecimal::from_scaled(0, 306494352, 14),  //11
+                FixedDecimal::from_scaled(0, -127834049, 15), //12
             ],
         }
     }
 }
 
+impl<T: FixedPrecision> CDFCustomAprox<T> {
+    /// Survival function `1 - Φ(x)`. From `x = 3` on the upper tail comes
+    /// from `normal_sf_tail` with relative rather than absolute accuracy;
+    /// subtracting the CDF from one would keep the fit's absolute error of
+    /// about 1e-9, which is already 3e-5 relative at `x = 4`.
+    pub fn sf(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        if x < 0 {
+            return self.evaluate(-x);
+        }
+        if x < SF_TAIL_START {
+            return FixedDecimal::<T>::one() - self.evaluate(x);
+        }
+        normal_sf_tail(x)
+    }
+}
+
+impl<T: FixedPrecision> Default for CDFCustomAprox<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: FixedPrecision> Function<T> for CDFCustomAprox<T> {
     fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
-        if x < FixedDecimal::<T>::from_str("-6").unwrap() {
+        if x < -6 {
             return FixedDecimal::<T>::zero();
         }
-        if x > FixedDecimal::<T>::from_str("6").unwrap() {
+        if x > 6 {
             return FixedDecimal::<T>::one();
         }
         topher_cdf(x, &self.coefficients)
     }
 }
 
-impl<T: FixedPrecision> TryFunction<T> for CDFCustomAprox<T> {
-    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
-        Ok(self.evaluate(x)) // evaluation itself is safe within given domain
+/// Normal CDF from the error function, `Φ(x) = (1 + erf(x/√2)) / 2`,
+/// evaluated as `erfc(-x/√2) / 2` so the lower tail keeps its relative
+/// accuracy. Accurate to about 1e-16 at F18, where `CDFCustomAprox` carries
+/// the fit's error of about 1e-9.
+pub struct CDFErf<T: FixedPrecision> {
+    frac_1_sqrt_2: FixedDecimal<T>,
+}
+
+impl<T: FixedPrecision> CDFErf<T> {
+    pub fn new() -> Self {
+        Self {
+            frac_1_sqrt_2: const {
+                FixedDecimal::from_scaled(0, 707106781186547524400844362104849039, 36)
+            },
+        }
+    }
+}
+
+impl<T: FixedPrecision> Default for CDFErf<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FixedPrecision> Function<T> for CDFErf<T> {
+    fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        // erfc is already flat past 9 = 12.7 / √2; clamping first keeps the
+        // scaling below from overflowing for huge inputs
+        let limit = FixedDecimal::<T>::from_i128(13);
+        erfc(-(x.clamp(-limit, limit) * self.frac_1_sqrt_2)) / 2
     }
 }
 
@@ -67,9 +120,171 @@ pub fn topher_cdf<T: FixedPrecision>(
     }
     let f = x.polynomial(coefficients);
     let denominator_exponent = range_reduce_taylor_exp::<T, 30>(-f);
-    let result = FixedDecimal::<T>::one() / (FixedDecimal::<T>::one() + denominator_exponent);
-    result
+    FixedDecimal::<T>::one() / (FixedDecimal::<T>::one() + denominator_exponent)
+}
+
+const SF_TAIL_START: i128 = 3;
+
+// Continued fraction for the Mills ratio,
+// 1 - Φ(x) = φ(x) / (x + 1/(x + 2/(x + 3/(x + ...)))), evaluated from the
+// back. Sixty terms reach full F18 precision for x >= SF_TAIL_START.
+fn normal_sf_tail<T: FixedPrecision>(x: FixedDecimal<T>) -> FixedDecimal<T> {
+    let mut denominator = x;
+    for k in (1..=60).rev() {
+        denominator = x + FixedDecimal::<T>::from_i128(k) / denominator;
+    }
+    pdf(x) / denominator
+}
+
+/// Inverse of the standard normal CDF using Acklam's rational approximation
+/// (relative error below 1.2e-9). `p` must lie strictly inside `(0, 1)`.
+///
+/// The central rational cancels down to values around 0.003, so at low
+/// precisions such as F9 the result only carries about six correct decimals.
+pub fn probit<T: FixedPrecision>(p: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+    if p <= 0 || p >= 1 {
+        return Err(FixedFastError::DomainError(
+            "probit is only defined on the open interval (0, 1)",
+        ));
+    }
+    let p_low = const { FixedDecimal::<T>::from_scaled(0, 2425, 5) };
+    if p < p_low {
+        return probit_tail(p);
+    }
+    if p > FixedDecimal::<T>::one() - p_low {
+        return Ok(-probit_tail(FixedDecimal::<T>::one() - p)?);
+    }
+    let q = p - const { FixedDecimal::<T>::from_scaled(0, 5, 1) };
+    let r = q.squared();
+    let numerator = r.polynomial(
+        &const {
+            [
+                FixedDecimal::from_scaled(2, 506628277459239, 15),
+                FixedDecimal::from_scaled(-30, -66479806614716, 14),
+                FixedDecimal::from_scaled(138, 3577518672690, 13),
+                FixedDecimal::from_scaled(-275, -9285104469687, 13),
+                FixedDecimal::from_scaled(220, 9460984245205, 13),
+                FixedDecimal::from_scaled(-39, -69683028665376, 14),
+            ]
+        },
+    );
+    let denominator = r.polynomial(
+        &const {
+            [
+                FixedDecimal::one(),
+                FixedDecimal::from_scaled(-13, -28068155288572, 14),
+                FixedDecimal::from_scaled(66, 80131188771972, 14),
+                FixedDecimal::from_scaled(-155, -6989798598866, 13),
+                FixedDecimal::from_scaled(161, 5858368580409, 13),
+                FixedDecimal::from_scaled(-54, -47609879822406, 14),
+            ]
+        },
+    );
+    (numerator * q).checked_div(denominator)
+}
+
+// lower tail of the probit, for p below 0.02425
+fn probit_tail<T: FixedPrecision>(p: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+    let q = sqrt_newton_raphson_try::<T, 12>(-range_reduce_arctanh_ln_try::<T, 20>(p)? * 2)?;
+    let numerator = q.polynomial(
+        &const {
+            [
+                FixedDecimal::from_scaled(2, 938163982698783, 15),
+                FixedDecimal::from_scaled(4, 374664141464968, 15),
+                FixedDecimal::from_scaled(-2, -549732539343734, 15),
+                FixedDecimal::from_scaled(-2, -400758277161838, 15),
+                FixedDecimal::from_scaled(0, -3223964580411365, 16),
+                FixedDecimal::from_scaled(0, -7784894002430293, 18),
+            ]
+        },
+    );
+    let denominator = q.polynomial(
+        &const {
+            [
+                FixedDecimal::one(),
+                FixedDecimal::from_scaled(3, 754408661907416, 15),
+                FixedDecimal::from_scaled(2, 445134137142996, 15),
+                FixedDecimal::from_scaled(0, 3224671290700398, 16),
+                FixedDecimal::from_scaled(0, 7784695709041462, 18),
+            ]
+        },
+    );
+    numerator.checked_div(denominator)
+}
+
+/// Bivariate standard normal CDF `P(X <= a, Y <= b)` for correlation `rho`,
+/// which must lie strictly inside `(-1, 1)`.
+///
+/// Uses Drezner and Wesolowsky's single-integral form
+/// `Φ(a)Φ(b) + 1/(2π) ∫_0^rho exp(-(a² - 2rab + b²) / (2(1 - r²))) / sqrt(1 - r²) dr`
+/// with `CDFCustomAprox` for the marginals. The integral is split into
+/// segments on which `1 - |r|` halves, each covered by a 10-point
+/// Gauss-Legendre rule, so the accuracy holds up as `|rho|` approaches one at
+/// the cost of one extra segment per halving.
+pub fn bivariate_normal_cdf<T: FixedPrecision>(
+    a: FixedDecimal<T>,
+    b: FixedDecimal<T>,
+    rho: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    let one = FixedDecimal::<T>::one();
+    if rho <= -1 || rho >= 1 {
+        return Err(FixedFastError::DomainError(
+            "bivariate normal correlation must lie in (-1, 1)",
+        ));
+    }
+    let cdf = CDFCustomAprox::new();
+    let independent = cdf.evaluate(a) * cdf.evaluate(b);
+    if rho == 0 {
+        return Ok(independent);
+    }
+
+    let nodes: [FixedDecimal<T>; 5] = const {
+        [
+            FixedDecimal::from_scaled(0, 1488743389816312, 16),
+            FixedDecimal::from_scaled(0, 4333953941292472, 16),
+            FixedDecimal::from_scaled(0, 6794095682990244, 16),
+            FixedDecimal::from_scaled(0, 8650633666889845, 16),
+            FixedDecimal::from_scaled(0, 9739065285171717, 16),
+        ]
+    };
+    let weights: [FixedDecimal<T>; 5] = const {
+        [
+            FixedDecimal::from_scaled(0, 2955242247147529, 16),
+            FixedDecimal::from_scaled(0, 2692667193099963, 16),
+            FixedDecimal::from_scaled(0, 2190863625159820, 16),
+            FixedDecimal::from_scaled(0, 1494513491505806, 16),
+            FixedDecimal::from_scaled(0, 666713443086881, 16),
+        ]
+    };
+    let sign = rho.signum();
+    let integrand = |u: FixedDecimal<T>| -> Result<FixedDecimal<T>> {
+        let r = u * sign;
+        let one_minus_r2 = one - r.squared();
+        let cross: FixedDecimal<T> = r * a * b * 2;
+        let exponent = -(a.squared() - cross + b.squared()) / (one_minus_r2 * 2);
+        if exponent < -3 * T::PRECISION as i128 {
+            return Ok(FixedDecimal::zero());
+        }
+        range_reduce_taylor_exp::<T, 20>(exponent)
+            .checked_div(sqrt_newton_raphson_try::<T, 20>(one_minus_r2)?)
+    };
+
+    let end = rho.abs();
+    let mut lower = FixedDecimal::<T>::zero();
+    let mut integral = FixedDecimal::<T>::zero();
+    while lower < end {
+        let upper = end.min(one - (one - lower) / 2);
+        let (mid, half) = ((lower + upper) / 2, (upper - lower) / 2);
+        for (node, weight) in nodes.into_iter().zip(weights) {
+            let offset = half * node;
+            let pair = integrand(mid - offset)? + integrand(mid + offset)?;
+            integral += half * weight * pair;
+        }
+        lower = upper;
+    }
+    Ok(independent + integral * sign / (2 * FixedDecimal::<T>::pi()))
 }
+
 pub struct CDFLinearInterpLookupTable<T: FixedPrecision> {
     lookup: LookupTable<T>,
 }
@@ -83,6 +298,55 @@ impl<T: FixedPrecision> CDFLinearInterpLookupTable<T> {
             }),
         }
     }
+
+    /// Builds the table from `builder`, which must start at zero since
+    /// negative inputs are mirrored. Inputs beyond `end` evaluate to one
+    /// whatever the extrapolation mode.
+    pub fn from_builder(builder: LookupTableBuilder<T>) -> Result<Self> {
+        if builder.start != FixedDecimal::zero() {
+            return Err(FixedFastError::DomainError("CDF table must start at zero"));
+        }
+        let custom_aprox = CDFCustomAprox::new();
+        Ok(Self {
+            lookup: builder.build(|x| custom_aprox.evaluate(x))?,
+        })
+    }
+}
+
+impl<T: FixedPrecision> CDFLinearInterpLookupTable<T> {
+    /// Survival function `1 - Φ(x)`, switching to the same tail expansion
+    /// as `CDFCustomAprox::sf` from `x = 3` so the table's absolute error
+    /// does not swamp small tail probabilities.
+    pub fn sf(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        if x < 0 {
+            return self.evaluate(-x);
+        }
+        if x < SF_TAIL_START {
+            return FixedDecimal::<T>::one() - self.evaluate(x);
+        }
+        normal_sf_tail(x)
+    }
+
+    /// The interpolated CDF with the slope of its table segment, an
+    /// estimate of the density. The slope is zero from `end` on.
+    pub fn evaluate_with_slope(
+        &self,
+        x: FixedDecimal<T>,
+    ) -> Result<(FixedDecimal<T>, FixedDecimal<T>)> {
+        if x < 0 {
+            return self
+                .evaluate_with_slope(-x)
+                .map(|(value, slope)| (FixedDecimal::<T>::one() - value, slope));
+        }
+        if x >= self.lookup.end() {
+            return Ok((FixedDecimal::one(), FixedDecimal::zero()));
+        }
+        let index = self.lookup.get_index(x)?;
+        Ok(match self.segment(index) {
+            Some([x1, x2, y1, y2]) => linear_interpolation_with_slope(x, x1, x2, y1, y2),
+            None => (self.interpolate(x, index), FixedDecimal::zero()),
+        })
+    }
 }
 
 impl<T: FixedPrecision> Function<T> for CDFLinearInterpLookupTable<T> {
@@ -94,43 +358,50 @@ impl<T: FixedPrecision> Function<T> for CDFLinearInterpLookupTable<T> {
             return FixedDecimal::<T>::one();
         }
         let index = self.lookup.get_index(x).expect("Index not found");
-        if index + 1 >= self.lookup.table.len() {
-            return self.lookup.table[index];
-        }
-        let lower_value = self.lookup.step_size() * index + self.lookup.start();
-        linear_interpolation(
-            x,
-            lower_value,
-            lower_value + self.lookup.step_size(),
-            self.lookup.table[index],
-            self.lookup.table[index + 1],
-        )
+        self.interpolate(x, index)
     }
-}
 
-impl<T: FixedPrecision> TryFunction<T> for CDFLinearInterpLookupTable<T> {
-    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
         if x < 0 {
-            return self.try_evaluate(-x).map(|v| FixedDecimal::<T>::one() - v);
+            return Function::evaluate_checked(self, -x).map(|v| FixedDecimal::<T>::one() - v);
         }
         if x >= self.lookup.end() {
             return Ok(FixedDecimal::<T>::one());
         }
         let index = self.lookup.get_index(x)?;
-        if index + 1 >= self.lookup.table.len() {
-            return Ok(self.lookup.table[index]);
+        Ok(self.interpolate(x, index))
+    }
+}
+
+impl<T: FixedPrecision> CDFLinearInterpLookupTable<T> {
+    // The last sample sits below `end`, so the final bucket interpolates up
+    // to `end` itself, where the CDF is pinned to one. This keeps the table
+    // continuous at the edge instead of holding the last sample flat, as
+    // long as `end` lies in the saturated tail.
+    fn interpolate(&self, x: FixedDecimal<T>, index: usize) -> FixedDecimal<T> {
+        match self.segment(index) {
+            Some([x1, x2, y1, y2]) => linear_interpolation(x, x1, x2, y1, y2),
+            None => self.lookup.table[index.min(self.lookup.table.len() - 1)],
         }
+    }
+
+    // the `[x1, x2, y1, y2]` endpoints of bucket `index`, or `None` in
+    // `Step` mode
+    fn segment(&self, index: usize) -> Option<[FixedDecimal<T>; 4]> {
+        if self.lookup.interpolation == Interpolation::Step {
+            return None;
+        }
+        let index = index.min(self.lookup.table.len() - 1);
         let lower_value = self.lookup.step_size() * index + self.lookup.start();
-        Ok(linear_interpolation(
-            x,
-            lower_value,
-            lower_value + self.lookup.step_size(),
-            self.lookup.table[index],
-            self.lookup.table[index + 1],
-        ))
+        let (upper_value, upper) = match self.lookup.table.get(index + 1) {
+            Some(&upper) => (lower_value + self.lookup.step_size(), upper),
+            None => (self.lookup.end(), FixedDecimal::<T>::one()),
+        };
+        Some([lower_value, upper_value, self.lookup.table[index], upper])
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -141,6 +412,55 @@ mod tests {
         const PRECISION: u32 = 9;
     }
 
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F14;
+
+    impl FixedPrecision for F14 {
+        const PRECISION: u32 = 14;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    fn parse_coefficients<T: FixedPrecision>(coefficients: &[&str]) -> Vec<FixedDecimal<T>> {
+        coefficients
+            .iter()
+            .map(|c| FixedDecimal::from_str(c).unwrap())
+            .collect()
+    }
+
+    fn assert_coefficients_match_parsed<T: FixedPrecision + std::fmt::Debug>() {
+        let parsed = parse_coefficients::<T>(&[
+            "-0.00000000436953479",
+            "1.59576962000000000",
+            "-0.00000955404601",
+            "0.07274169990000000",
+            "-0.00026565023900000",
+            "0.00050857094000000",
+            "-0.00079653385500000",
+            "0.00059778488700000",
+            "-0.00040077230600000",
+            "0.00014965658000000",
+            "-0.00002988796070000",
+            "0.00000306494352000",
+            "-0.00000012783404900",
+        ]);
+        assert_eq!(CDFCustomAprox::<T>::new().coefficients.to_vec(), parsed);
+    }
+
+    #[test]
+    fn test_const_coefficients() {
+        const CDF: CDFCustomAprox<F9> = CDFCustomAprox::new();
+        assert_coefficients_match_parsed::<F9>();
+        assert_coefficients_match_parsed::<F18>();
+        let x = FixedDecimal::<F9>::from_str("1.16685").unwrap();
+        assert_eq!(CDF.evaluate(x), CDFCustomAprox::new().evaluate(x));
+    }
+
     #[test]
     fn test_cdf() {
         let cdf = CDFCustomAprox::new();
@@ -152,10 +472,43 @@ mod tests {
         let x = FixedDecimal::<F9>::from_str("-1.12313512").unwrap();
         assert_eq!(
             cdf.evaluate(x),
-            FixedDecimal::<F9>::from_str("0.130690057273233524").unwrap()
+            FixedDecimal::<F9>::from_str("0.130690056200254600").unwrap()
         );
     }
 
+    #[test]
+    fn test_cdf_erf() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let cdf = CDFErf::new();
+        let polynomial = CDFCustomAprox::new();
+        let cases = [
+            ("-4", "0.000031671241833119921254"),
+            ("-2.5", "0.006209665325776135167"),
+            ("-1.16685", "0.12163547544243823487"),
+            ("-0.3", "0.38208857781104736269"),
+            ("0", "0.5"),
+            ("0.5", "0.69146246127401310364"),
+            ("1.16685", "0.87836452455756176513"),
+            ("2", "0.9772498680518207928"),
+            ("3.3", "0.9995165758576162228"),
+            ("5", "0.99999971334842812081"),
+            ("7", "0.99999999999872018746"),
+        ];
+        for (x, expected) in cases {
+            let value = cdf.evaluate(d(x));
+            let error = (value - d(expected)).abs();
+            assert!(error < d("0.0000000000000001"));
+            // the polynomial fit is good to about 1e-9, and never closer
+            // than the erf form
+            let fit_error = (polynomial.evaluate(d(x)) - d(expected)).abs();
+            assert!(fit_error < d("0.00000001"));
+            assert!(error <= fit_error);
+        }
+        assert_eq!(cdf.evaluate(d("12")), FixedDecimal::one());
+        assert_eq!(cdf.evaluate(d("-12")), FixedDecimal::zero());
+        assert_eq!(cdf.evaluate(d("1000000")), FixedDecimal::one());
+    }
+
     #[test]
     fn test_cdf_linear_interp_lookup_table() {
         let table = CDFLinearInterpLookupTable::<F9>::new(
@@ -167,4 +520,184 @@ mod tests {
             FixedDecimal::<F9>::from_str("0.130690058").unwrap()
         );
     }
+
+    #[test]
+    fn test_cdf_linear_interp_lookup_table_top_edge() {
+        let end = FixedDecimal::<F9>::from_str("6").unwrap();
+        let table = CDFLinearInterpLookupTable::<F9>::new(
+            end,
+            FixedDecimal::<F9>::from_str("0.001").unwrap(),
+        );
+        let ulp = FixedDecimal::<F9>::ulp();
+        let tolerance = FixedDecimal::<F9>::from_str("0.000000002").unwrap();
+        assert_eq!(table.evaluate(end), FixedDecimal::one());
+        assert_eq!(table.evaluate(-end), FixedDecimal::zero());
+        let mut previous = table.evaluate(end - FixedDecimal::from_str("0.0015").unwrap());
+        for offset in ["0.001", "0.0005", "0.000001"] {
+            let value = table.evaluate(end - FixedDecimal::from_str(offset).unwrap());
+            assert!(value >= previous && value <= FixedDecimal::one());
+            previous = value;
+        }
+        let below_end = table.evaluate(end - ulp);
+        assert!(below_end >= previous);
+        assert!(FixedDecimal::<F9>::one() - below_end < tolerance);
+        assert!(table.evaluate_checked(end - ulp).is_ok());
+
+        // an `end` off the step grid leaves a short final bucket
+        let table = CDFLinearInterpLookupTable::<F9>::new(
+            FixedDecimal::<F9>::from_str("6.0005").unwrap(),
+            FixedDecimal::<F9>::from_str("0.001").unwrap(),
+        );
+        let below_end = table.evaluate(FixedDecimal::<F9>::from_str("6.0004").unwrap());
+        assert!(FixedDecimal::<F9>::one() - below_end < tolerance);
+    }
+
+    #[test]
+    fn test_map_lookup_table_to_complement() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let table = CDFLinearInterpLookupTable::<F9>::new(d("6"), d("0.01"));
+        let complement = table.lookup.map(|v| FixedDecimal::one() - v);
+        assert_eq!(complement.table.len(), table.lookup.table.len());
+        assert_eq!(complement.step_size, table.lookup.step_size);
+        assert_eq!(complement.end, table.lookup.end);
+        for x in ["0", "0.01", "0.123456", "1.5", "2.999", "5.98"] {
+            let x = d(x);
+            assert_eq!(
+                complement.try_evaluate(x).unwrap(),
+                FixedDecimal::one() - table.evaluate(x)
+            );
+        }
+        assert!(complement.try_evaluate(d("6.5")).is_err());
+    }
+
+    #[test]
+    fn test_affine_wrap_normal_cdf() {
+        use crate::function::AffineWrap;
+
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let (mu, sigma) = (d("1"), d("2"));
+        let normal = AffineWrap::new(CDFV1::<F9>::new(d("6"), d("0.001")))
+            .input(FixedDecimal::one() / sigma, -mu / sigma);
+        let standard = normal.inner();
+        assert_eq!(normal.evaluate(mu), standard.evaluate(FixedDecimal::zero()));
+        for x in ["-7", "-2.5", "0", "1.7", "3", "4.21", "20"] {
+            let x = d(x);
+            assert_eq!(normal.evaluate(x), standard.evaluate((x - mu) / sigma));
+            assert_eq!(
+                Function::evaluate_checked(&normal, x).unwrap(),
+                normal.evaluate(x)
+            );
+        }
+        let exact = CDFCustomAprox::<F9>::new();
+        assert!((normal.evaluate(d("3")) - exact.evaluate(d("1"))).abs() < d("0.000001"));
+
+        // the complement through the output transform
+        let upper = AffineWrap::new(CDFV1::<F9>::new(d("6"), d("0.001")))
+            .output(-FixedDecimal::one(), FixedDecimal::one());
+        assert_eq!(
+            upper.evaluate(d("0.25")),
+            d("1") - standard.evaluate(d("0.25"))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_with_slope() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let table = CDFLinearInterpLookupTable::<F9>::new(d("6"), d("0.001"));
+        for x in ["0.3", "1", "2.5"] {
+            let x = d(x);
+            let (value, slope) = table.evaluate_with_slope(x).unwrap();
+            assert_eq!(value, table.evaluate(x));
+            assert!((slope - pdf(x)).abs() < d("0.001"));
+            let (mirrored, mirrored_slope) = table.evaluate_with_slope(-x).unwrap();
+            assert_eq!(mirrored, table.evaluate(-x));
+            assert_eq!(mirrored_slope, slope);
+        }
+        assert_eq!(
+            table.evaluate_with_slope(d("7")).unwrap(),
+            (FixedDecimal::one(), FixedDecimal::zero())
+        );
+    }
+
+    #[test]
+    fn test_sf() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let cdf = CDFCustomAprox::new();
+        let x = d("4");
+        let expected = d("0.000031671241833119965");
+        let direct = cdf.sf(x);
+        let subtracted = FixedDecimal::one() - cdf.evaluate(x);
+        assert!((direct - expected).abs() < d("0.000000000000001"));
+        assert!((subtracted - expected).abs() > d("0.0000000001"));
+
+        // beyond the fit's range the subtraction is exactly zero
+        assert_eq!(
+            FixedDecimal::one() - cdf.evaluate(d("8")),
+            FixedDecimal::zero()
+        );
+        let tail = cdf.sf(d("8"));
+        assert!((tail - d("0.0000000000000006220960574271819")).abs() < d("0.000000000000000002"));
+
+        assert!((cdf.sf(d("1")) - d("0.15865525393145707")).abs() < d("0.000000001"));
+        assert_eq!(cdf.sf(d("-1.5")), cdf.evaluate(d("1.5")));
+
+        let table = CDFLinearInterpLookupTable::<F18>::new(d("6"), d("0.001"));
+        assert_eq!(table.sf(x), direct);
+        assert_eq!(table.sf(d("-1.5")), table.evaluate(d("1.5")));
+    }
+
+    #[test]
+    fn test_bivariate_normal_cdf() {
+        let d = |s| FixedDecimal::<F14>::from_str(s).unwrap();
+        let cases = [
+            ("0", "0", "0.5", "0.33333333333333337"),
+            ("1", "-1", "0.3", "0.1483382090574221"),
+            ("0.5", "1.2", "-0.7", "0.577918150871023"),
+            ("-1.5", "-0.5", "0.95", "0.06679091007632729"),
+            ("2", "2", "0.999", "0.9762868430442765"),
+            ("0.3", "-0.2", "-0.99", "0.0463814848243807"),
+            ("1", "1", "0.9999", "0.8399795724457424"),
+        ];
+        for (a, b, rho, expected) in cases {
+            let value = bivariate_normal_cdf(d(a), d(b), d(rho)).unwrap();
+            assert!((value - d(expected)).abs() < d("0.0000001"));
+        }
+        // independence factorizes
+        let cdf = CDFCustomAprox::new();
+        assert_eq!(
+            bivariate_normal_cdf(d("0.4"), d("-0.9"), FixedDecimal::zero()).unwrap(),
+            cdf.evaluate(d("0.4")) * cdf.evaluate(d("-0.9"))
+        );
+        assert!(matches!(
+            bivariate_normal_cdf(d("0"), d("0"), d("1")),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(bivariate_normal_cdf(d("0"), d("0"), d("-1.5")).is_err());
+    }
+
+    #[test]
+    fn test_probit() {
+        let cases = [
+            ("0.5", "0"),
+            ("0.975", "1.959963984540"),
+            ("0.025", "-1.959963984540"),
+            ("0.001", "-3.090232306168"),
+            ("0.999", "3.090232306168"),
+            ("0.8413447460685", "1"),
+        ];
+        for (p, expected) in cases {
+            let x = probit(FixedDecimal::<F14>::from_str(p).unwrap()).unwrap();
+            assert!(
+                (x - FixedDecimal::from_str(expected).unwrap()).abs()
+                    < FixedDecimal::from_str("0.00000001").unwrap()
+            );
+            let x = probit(FixedDecimal::<F9>::from_str(p).unwrap()).unwrap();
+            assert!(
+                (x - FixedDecimal::from_str(expected).unwrap()).abs()
+                    < FixedDecimal::from_str("0.000001").unwrap()
+            );
+        }
+        assert!(probit(FixedDecimal::<F9>::zero()).is_err());
+        assert!(probit(FixedDecimal::<F9>::one()).is_err());
+    }
 }
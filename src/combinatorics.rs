@@ -0,0 +1,211 @@
+use std::marker::PhantomData;
+
+use crate::{
+    error::{FixedFastError, Result},
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    function::IntFunction,
+    wide::I256,
+};
+
+/// `n!` as a fixed-point value. The product is formed in 256 bits, so the
+/// only failure is `Overflow` when the scaled result does not fit `T`
+/// (beyond `21!` at F18 or `27!` at F9).
+pub fn factorial<T: FixedPrecision>(n: u32) -> Result<FixedDecimal<T>> {
+    let mut product = I256::ONE;
+    for i in 2..=n {
+        product = product
+            .checked_mul(I256::from_i128(i as i128))
+            .ok_or(FixedFastError::Overflow)?;
+    }
+    to_fixed(product)
+}
+
+/// Binomial coefficient `C(n, k)`, which is zero when `k > n`. Uses the
+/// multiplicative formula over the smaller of `k` and `n - k`; every
+/// partial product is itself a binomial coefficient, so each division is
+/// exact.
+pub fn binomial<T: FixedPrecision>(n: u32, k: u32) -> Result<FixedDecimal<T>> {
+    if k > n {
+        return Ok(FixedDecimal::zero());
+    }
+    let k = k.min(n - k);
+    let mut coefficient = I256::ONE;
+    for i in 1..=k {
+        coefficient = coefficient
+            .checked_mul_div(
+                I256::from_i128((n - k + i) as i128),
+                I256::from_i128(i as i128),
+            )
+            .ok_or(FixedFastError::Overflow)?;
+    }
+    to_fixed(coefficient)
+}
+
+/// Number of length-`k` sequences drawn with repetition from `n` symbols,
+/// `n^k`. Exact, or `Overflow` when the scaled result does not fit `T`.
+pub fn arrangements<T: FixedPrecision>(n: u32, k: u32) -> Result<FixedDecimal<T>> {
+    FixedDecimal::ipow(n as i128, k)
+}
+
+/// `factorial` as an `IntFunction`. Negative arguments are a
+/// `DomainError`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Factorial<T: FixedPrecision> {
+    _precision: PhantomData<T>,
+}
+
+impl<T: FixedPrecision> Factorial<T> {
+    pub fn new() -> Self {
+        Self {
+            _precision: PhantomData,
+        }
+    }
+}
+
+impl<T: FixedPrecision> IntFunction<T> for Factorial<T> {
+    fn evaluate_int(&self, n: i128) -> FixedDecimal<T> {
+        self.try_evaluate_int(n)
+            .expect("factorial is undefined here")
+    }
+
+    fn try_evaluate_int(&self, n: i128) -> Result<FixedDecimal<T>> {
+        if n < 0 {
+            return Err(FixedFastError::DomainError(
+                "factorial is undefined for negative numbers",
+            ));
+        }
+        factorial(u32::try_from(n).map_err(|_| FixedFastError::Overflow)?)
+    }
+}
+
+/// `C(n, k)` for a fixed `n` as an `IntFunction` of `k`, which is zero for
+/// `k` outside `0..=n`.
+#[derive(Debug, Clone, Copy)]
+pub struct BinomialCoefficient<T: FixedPrecision> {
+    n: u32,
+    _precision: PhantomData<T>,
+}
+
+impl<T: FixedPrecision> BinomialCoefficient<T> {
+    pub fn new(n: u32) -> Self {
+        Self {
+            n,
+            _precision: PhantomData,
+        }
+    }
+}
+
+impl<T: FixedPrecision> IntFunction<T> for BinomialCoefficient<T> {
+    fn evaluate_int(&self, k: i128) -> FixedDecimal<T> {
+        self.try_evaluate_int(k)
+            .expect("binomial coefficient overflowed")
+    }
+
+    fn try_evaluate_int(&self, k: i128) -> Result<FixedDecimal<T>> {
+        match u32::try_from(k) {
+            Ok(k) => binomial(self.n, k),
+            Err(_) => Ok(FixedDecimal::zero()),
+        }
+    }
+}
+
+fn to_fixed<T: FixedPrecision>(x: I256) -> Result<FixedDecimal<T>> {
+    x.checked_mul(I256::from_i128(FixedDecimal::<T>::scale()))
+        .and_then(I256::to_i128)
+        .map(FixedDecimal::from_raw)
+        .ok_or(FixedFastError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    #[test]
+    fn test_factorial() {
+        assert_eq!(factorial::<F9>(0).unwrap(), FixedDecimal::one());
+        assert_eq!(factorial::<F9>(5).unwrap(), FixedDecimal::from_i128(120));
+        assert_eq!(
+            factorial::<F18>(21).unwrap(),
+            FixedDecimal::from_i128(51_090_942_171_709_440_000)
+        );
+        assert!(matches!(
+            factorial::<F18>(22),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(factorial::<F9>(27).is_ok());
+        assert!(matches!(factorial::<F9>(28), Err(FixedFastError::Overflow)));
+        assert!(matches!(
+            factorial::<F9>(u32::MAX),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_int_functions() {
+        let factorial = Factorial::<F9>::new();
+        assert_eq!(factorial.evaluate_int(6), FixedDecimal::from_i128(720));
+        assert!(matches!(
+            factorial.try_evaluate_int(-1),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(matches!(
+            factorial.try_evaluate_int(28),
+            Err(FixedFastError::Overflow)
+        ));
+
+        let row = BinomialCoefficient::<F9>::new(6);
+        let expected = [0, 1, 6, 15, 20, 15, 6, 1, 0];
+        for (k, expected) in (-1..=7).zip(expected) {
+            assert_eq!(row.evaluate_int(k), FixedDecimal::from_i128(expected));
+        }
+    }
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial::<F9>(10, 3).unwrap(), FixedDecimal::from_i128(120));
+        assert_eq!(binomial::<F9>(10, 7).unwrap(), FixedDecimal::from_i128(120));
+        assert_eq!(binomial::<F9>(10, 0).unwrap(), FixedDecimal::one());
+        assert_eq!(binomial::<F9>(3, 4).unwrap(), FixedDecimal::zero());
+        // 100! overflows but C(100, 50) is only about 1e29
+        assert_eq!(
+            binomial::<F9>(100, 50).unwrap(),
+            FixedDecimal::from_i128(100_891_344_545_564_193_334_812_497_256)
+        );
+        assert!(matches!(
+            binomial::<F18>(200, 100),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_arrangements() {
+        assert_eq!(
+            arrangements::<F9>(2, 10).unwrap(),
+            FixedDecimal::from_i128(1024)
+        );
+        assert_eq!(arrangements::<F9>(26, 0).unwrap(), FixedDecimal::one());
+        assert_eq!(arrangements::<F9>(0, 3).unwrap(), FixedDecimal::zero());
+        assert_eq!(
+            arrangements::<F18>(10, 20).unwrap(),
+            FixedDecimal::from_i128(100_000_000_000_000_000_000)
+        );
+        assert!(matches!(
+            arrangements::<F18>(10, 21),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+}
@@ -10,6 +10,8 @@ pub enum FixedFastError {
     DivideByZero,
     #[error("arithmetic overflow")]
     Overflow,
+    #[error("lookup table of {0} samples exceeds the size limit")]
+    TableTooLarge(usize),
 }
 
 // Provide automatic conversion from core int errors if needed
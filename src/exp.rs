@@ -2,11 +2,10 @@ use std::marker::PhantomData;
 
 use crate::{
     FixedDecimal,
-    error::Result,
+    error::{FixedFastError, Result},
     fixed_decimal::FixedPrecision,
-    function::{Function, TryFunction},
-    interpolation::linear_interpolation,
-    lookup_table::LookupTable,
+    function::Function,
+    lookup_table::{LookupTable, LookupTableBuilder},
 };
 
 pub type ExpV1<T> = ExpLinearInterpLookupTable<T, 10>;
@@ -22,19 +21,53 @@ impl<T: FixedPrecision, const TAYLOR_ORDER: u32> ExpRangeReduceTaylor<T, TAYLOR_
     }
 }
 
+impl<T: FixedPrecision, const TAYLOR_ORDER: u32> Default for ExpRangeReduceTaylor<T, TAYLOR_ORDER> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: FixedPrecision, const TAYLOR_ORDER: u32> Function<T>
     for ExpRangeReduceTaylor<T, TAYLOR_ORDER>
 {
     fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
         range_reduce_taylor_exp::<T, TAYLOR_ORDER>(x)
     }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        range_reduce_taylor_exp_try::<T, TAYLOR_ORDER>(x)
+    }
 }
 
-impl<T: FixedPrecision, const TAYLOR_ORDER: u32> TryFunction<T>
-    for ExpRangeReduceTaylor<T, TAYLOR_ORDER>
-{
-    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
-        Ok(range_reduce_taylor_exp::<T, TAYLOR_ORDER>(x))
+/// Range-reduced Taylor exp whose order comes from
+/// `recommended_taylor_order`, so it cannot be under-iterated for `T`.
+pub struct ExpV2<T: FixedPrecision> {
+    _precision: PhantomData<T>,
+}
+
+impl<T: FixedPrecision> ExpV2<T> {
+    pub const TAYLOR_ORDER: u32 = recommended_taylor_order::<T>();
+
+    pub fn new() -> Self {
+        Self {
+            _precision: PhantomData,
+        }
+    }
+}
+
+impl<T: FixedPrecision> Default for ExpV2<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FixedPrecision> Function<T> for ExpV2<T> {
+    fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        taylor_exp(x, Self::TAYLOR_ORDER)
+    }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        taylor_exp_try(x, Self::TAYLOR_ORDER)
     }
 }
 
@@ -53,65 +86,167 @@ impl<T: FixedPrecision, const TAYLOR_ORDER: u32> ExpLinearInterpLookupTable<T, T
             ),
         }
     }
+
+    /// Builds the table from `builder`, whose extrapolation and interpolation
+    /// modes `evaluate` then follows.
+    pub fn from_builder(builder: LookupTableBuilder<T>) -> Result<Self> {
+        Ok(Self {
+            lookup: builder.build(range_reduce_taylor_exp::<T, TAYLOR_ORDER>)?,
+        })
+    }
+
+    /// The interpolated value with the slope of its table segment; see
+    /// `LookupTable::evaluate_with_slope`.
+    pub fn evaluate_with_slope(
+        &self,
+        x: FixedDecimal<T>,
+    ) -> Result<(FixedDecimal<T>, FixedDecimal<T>)> {
+        self.lookup.evaluate_with_slope(x)
+    }
 }
 
 impl<T: FixedPrecision, const TAYLOR_ORDER: u32> Function<T>
     for ExpLinearInterpLookupTable<T, TAYLOR_ORDER>
 {
     fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
-        let index = self.lookup.get_index(x).expect("Index not found");
-        if index + 1 >= self.lookup.table.len() {
-            return self.lookup.table[index];
-        }
-        let lower_value = self.lookup.step_size() * index + self.lookup.start();
-        linear_interpolation(
-            x,
-            lower_value,
-            lower_value + self.lookup.step_size(),
-            self.lookup.table[index],
-            self.lookup.table[index + 1],
-        )
+        self.lookup.try_evaluate(x).expect("Index not found")
+    }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        self.lookup.try_evaluate(x)
     }
 }
 
-impl<T: FixedPrecision, const TAYLOR_ORDER: u32> TryFunction<T>
-    for ExpLinearInterpLookupTable<T, TAYLOR_ORDER>
-{
-    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
-        let index = self.lookup.get_index(x)?;
-        if index + 1 >= self.lookup.table.len() {
-            return Ok(self.lookup.table[index]);
+// Largest |k| served from the precomputed 2^k table; covers every input
+// whose result is representable at the usual precisions.
+const TWO_POW_TABLE_K: i128 = 64;
+
+// Constants hoisted out of `range_reduce_taylor_exp`, evaluated once per
+// precision at compile time. Benchmark note: in a release build at F18 the
+// 20-term Taylor loop dominates and the measured difference per call is
+// within noise; the table mainly keeps shifts and the ln2 division off the
+// path for cheaper low-order variants.
+struct ExpConstants<T: FixedPrecision>(PhantomData<T>);
+
+impl<T: FixedPrecision> ExpConstants<T> {
+    const LN2: FixedDecimal<T> = FixedDecimal::ln2();
+    const LOG2_E: FixedDecimal<T> = FixedDecimal::log2_e();
+    const TWO_POW: [FixedDecimal<T>; 2 * TWO_POW_TABLE_K as usize + 1] = two_pow_table();
+}
+
+// same shifts as `two_pow_k`, for k in -TWO_POW_TABLE_K..=TWO_POW_TABLE_K
+const fn two_pow_table<T: FixedPrecision>() -> [FixedDecimal<T>; 2 * TWO_POW_TABLE_K as usize + 1] {
+    let mut table = [FixedDecimal::zero(); 2 * TWO_POW_TABLE_K as usize + 1];
+    let mut i = 0;
+    while i < table.len() {
+        let k = i as i128 - TWO_POW_TABLE_K;
+        let one = FixedDecimal::<T>::scale();
+        table[i] = FixedDecimal::from_raw(if k >= 0 { one << k } else { one >> -k });
+        i += 1;
+    }
+    table
+}
+
+fn two_pow<T: FixedPrecision>(k: i128) -> Result<FixedDecimal<T>> {
+    if k.abs() <= TWO_POW_TABLE_K {
+        Ok(ExpConstants::<T>::TWO_POW[(k + TWO_POW_TABLE_K) as usize])
+    } else if k < 0 {
+        // results below one ulp flush to zero
+        Ok(i32::try_from(k)
+            .ok()
+            .and_then(|k| FixedDecimal::checked_two_pow_k(k).ok())
+            .unwrap_or(FixedDecimal::zero()))
+    } else {
+        i32::try_from(k)
+            .map_err(|_| FixedFastError::Overflow)
+            .and_then(FixedDecimal::checked_two_pow_k)
+    }
+}
+
+// trunc(x / ln2), estimated with a multiply by log2(e). The estimate can be
+// one off next to a multiple of ln2, so it is nudged onto the truncated
+// quotient to keep results identical to dividing.
+fn reduction_index<T: FixedPrecision>(x: FixedDecimal<T>, ln2: FixedDecimal<T>) -> i128 {
+    let (x, ln2) = (x.to_raw(), ln2.to_raw());
+    let k = (FixedDecimal::<T>::from_raw(x) * ExpConstants::<T>::LOG2_E).to_i128();
+    if x >= 0 {
+        if (k + 1) * ln2 <= x {
+            k + 1
+        } else if k * ln2 > x {
+            k - 1
+        } else {
+            k
         }
-        let lower_value = self.lookup.step_size() * index + self.lookup.start();
-        Ok(linear_interpolation(
-            x,
-            lower_value,
-            lower_value + self.lookup.step_size(),
-            self.lookup.table[index],
-            self.lookup.table[index + 1],
-        ))
+    } else if (k - 1) * ln2 >= x {
+        k - 1
+    } else if k * ln2 < x {
+        k + 1
+    } else {
+        k
+    }
+}
+
+/// Smallest Taylor order whose truncation error after range reduction is
+/// below one ulp of `T`. The reduced argument is less than ln2 < 0.7 in
+/// magnitude, so the first omitted term is bounded by `0.7^n / n!`.
+pub const fn recommended_taylor_order<T: FixedPrecision>() -> u32 {
+    // the bound scaled so that one ulp is 10, leaving a digit of margin
+    let mut bound = 10i128.pow(T::PRECISION + 1);
+    let mut order = 0;
+    while bound > 0 {
+        order += 1;
+        bound = bound * 7 / (10 * order as i128);
     }
+    order
 }
 
 pub fn range_reduce_taylor_exp<T: FixedPrecision, const TAYLOR_ORDER: u32>(
     x: FixedDecimal<T>,
 ) -> FixedDecimal<T> {
-    let ln2 = FixedDecimal::<T>::ln2();
-    let k = (x / ln2).floor_i128();
+    taylor_exp(x, TAYLOR_ORDER)
+}
+
+/// `range_reduce_taylor_exp` that fails with `Overflow` when `e^x` does not
+/// fit in `T` instead of panicking. Results below one ulp are zero.
+pub fn range_reduce_taylor_exp_try<T: FixedPrecision, const TAYLOR_ORDER: u32>(
+    x: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    taylor_exp_try(x, TAYLOR_ORDER)
+}
+
+fn taylor_exp<T: FixedPrecision>(x: FixedDecimal<T>, order: u32) -> FixedDecimal<T> {
+    taylor_exp_try(x, order).expect("exp overflowed")
+}
+
+fn taylor_exp_try<T: FixedPrecision>(x: FixedDecimal<T>, order: u32) -> Result<FixedDecimal<T>> {
+    // e^89 is past i128::MAX and e^-89 below one ulp at every precision
+    let whole = x.to_i128();
+    if whole >= 89 {
+        return Err(FixedFastError::Overflow);
+    }
+    if whole <= -89 {
+        return Ok(FixedDecimal::zero());
+    }
+    let ln2 = ExpConstants::<T>::LN2;
+    let k = reduction_index(x, ln2);
     let r = x - ln2 * FixedDecimal::from_i128(k);
 
     let mut term = FixedDecimal::<T>::from_i128(1);
     let mut result = term;
-    for i in 1..=TAYLOR_ORDER {
+    for i in 1..=order {
         term = term * r / i;
         result += term;
     }
-    result * FixedDecimal::<T>::two_pow_k(k as i32)
+    result.checked_mul(two_pow(k)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        function::TryFunction,
+        lookup_table::{Extrapolation, Interpolation},
+    };
 
     #[derive(Debug, PartialEq, Eq, Clone, Copy)]
     struct F10;
@@ -120,6 +255,29 @@ mod tests {
         const PRECISION: u32 = 10;
     }
 
+    #[test]
+    fn test_range_reduce_taylor_exp_try() {
+        let d = |s| FixedDecimal::<F10>::from_str(s).unwrap();
+        for x in ["-1.231231", "0", "1", "20.5", "64.9"] {
+            assert_eq!(
+                range_reduce_taylor_exp_try::<F10, 20>(d(x)).unwrap(),
+                range_reduce_taylor_exp::<F10, 20>(d(x))
+            );
+        }
+        // F10 holds up to about 1.7e28, e^65.3
+        for x in ["65.5", "88.9", "89", "1000"] {
+            assert!(matches!(
+                range_reduce_taylor_exp_try::<F10, 20>(d(x)),
+                Err(FixedFastError::Overflow)
+            ));
+        }
+        assert_eq!(
+            range_reduce_taylor_exp_try::<F10, 20>(d("-1000")).unwrap(),
+            FixedDecimal::zero()
+        );
+        assert!(Function::evaluate_checked(&ExpV2::<F10>::new(), d("70")).is_err());
+    }
+
     #[test]
     fn test_range_reduce_taylor_exp() {
         let x = FixedDecimal::<F10>::from_str("1.0").unwrap();
@@ -139,6 +297,124 @@ mod tests {
         );
     }
 
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    // the implementation before the constants were hoisted
+    fn reference_exp<T: FixedPrecision>(x: FixedDecimal<T>) -> FixedDecimal<T> {
+        let ln2 = FixedDecimal::<T>::ln2();
+        let k = (x / ln2).to_i128();
+        let r = x - ln2 * FixedDecimal::from_i128(k);
+        let mut term = FixedDecimal::<T>::from_i128(1);
+        let mut result = term;
+        for i in 1..=20u32 {
+            term = term * r / i;
+            result += term;
+        }
+        result * FixedDecimal::<T>::two_pow_k(k as i32)
+    }
+
+    #[test]
+    fn test_hoisted_constants_leave_results_unchanged() {
+        let step = FixedDecimal::<F18>::from_str("0.0371").unwrap();
+        let mut x = FixedDecimal::<F18>::from_i128(-40);
+        while x < FixedDecimal::<F18>::from_i128(4) {
+            assert_eq!(range_reduce_taylor_exp::<F18, 20>(x), reference_exp(x));
+            x += step;
+        }
+        // the multiply by log2(e) is most likely to disagree with the
+        // division right next to multiples of ln2
+        for k in -50..=6 {
+            let boundary = FixedDecimal::<F18>::ln2() * FixedDecimal::from_i128(k);
+            for offset in -3..=3 {
+                let x = boundary + FixedDecimal::from_raw(offset);
+                assert_eq!(range_reduce_taylor_exp::<F18, 20>(x), reference_exp(x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_reduce_taylor_exp_underflow() {
+        // 2^-145 used to be a shift past the width of the raw value
+        let x = FixedDecimal::<F18>::from_i128(-100);
+        assert_eq!(range_reduce_taylor_exp::<F18, 20>(x), 0);
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F14;
+
+    impl FixedPrecision for F14 {
+        const PRECISION: u32 = 14;
+    }
+
+    fn assert_recommended_order_converged<T: FixedPrecision>(inputs: &[&str]) {
+        let exp = ExpV2::<T>::new();
+        for input in inputs {
+            let x = FixedDecimal::<T>::from_str(input).unwrap();
+            // extra terms are already below one ulp and change nothing
+            assert_eq!(
+                exp.evaluate(x),
+                taylor_exp(x, ExpV2::<T>::TAYLOR_ORDER + 20)
+            );
+        }
+    }
+
+    #[test]
+    fn test_recommended_taylor_order() {
+        assert_eq!(recommended_taylor_order::<F9>(), 12);
+        assert_eq!(recommended_taylor_order::<F14>(), 16);
+        assert_eq!(recommended_taylor_order::<F18>(), 19);
+        let inputs = ["-3.4", "-0.693", "0", "0.5", "0.6931", "1", "2.25", "4.1"];
+        assert_recommended_order_converged::<F9>(&inputs);
+        assert_recommended_order_converged::<F14>(&inputs);
+        assert_recommended_order_converged::<F18>(&inputs);
+    }
+
+    fn evaluate_fallibly<F: TryFunction<F10>>(
+        f: &F,
+        x: FixedDecimal<F10>,
+    ) -> Result<FixedDecimal<F10>> {
+        f.try_evaluate(x)
+    }
+
+    #[test]
+    fn test_function_usable_as_try_function() {
+        let exp = ExpRangeReduceTaylor::<F10, 20>::new();
+        let x = FixedDecimal::<F10>::from_str("-1.231231").unwrap();
+        assert_eq!(evaluate_fallibly(&exp, x).unwrap(), exp.evaluate(x));
+        // overridden fallible evaluation still reports errors
+        let ln = crate::ln::LnArcTanhExpansion::<F10, 20>::new();
+        assert!(evaluate_fallibly(&ln, FixedDecimal::from_i128(-1)).is_err());
+    }
+
+    #[test]
+    fn test_sample_range() {
+        let d = |s| FixedDecimal::<F10>::from_str(s).unwrap();
+        let exp = ExpRangeReduceTaylor::<F10, 20>::new();
+        let samples: Vec<_> = exp.sample_range(d("0"), d("1"), d("0.25")).collect();
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[0], (d("0"), FixedDecimal::one()));
+        assert_eq!(samples[4], (d("1"), exp.evaluate(d("1"))));
+        assert!((samples[4].1 - FixedDecimal::e()).abs() < d("0.0000001"));
+        for (x, y) in &samples {
+            assert_eq!(*y, exp.evaluate(*x));
+        }
+        // an end off the grid stops at the last point before it
+        let last = exp.sample_range(d("0"), d("0.9"), d("0.25")).last();
+        assert_eq!(last.unwrap().0, d("0.75"));
+    }
+
     #[test]
     fn test_exp_linear_interp_lookup_table() {
         let table = ExpLinearInterpLookupTable::<F10, 10>::new(
@@ -155,4 +431,174 @@ mod tests {
             FixedDecimal::<F10>::from_str("7.3890560972").unwrap()
         );
     }
+
+    #[test]
+    fn test_exp_lookup_table_builder() {
+        let d = |s| FixedDecimal::<F10>::from_str(s).unwrap();
+        let builder = LookupTableBuilder::new()
+            .start(d("-10"))
+            .end(d("10"))
+            .step_size(d("0.001"));
+        let built = ExpLinearInterpLookupTable::<F10, 10>::from_builder(builder).unwrap();
+        let table = ExpLinearInterpLookupTable::<F10, 10>::new(d("-10"), d("10"), d("0.001"));
+        assert_eq!(built.lookup.table, table.lookup.table);
+        for x in ["-10", "-1.12313512", "0", "2", "9.9995"] {
+            assert_eq!(built.evaluate(d(x)), table.evaluate(d(x)));
+        }
+        assert!(Function::evaluate_checked(&built, d("10.5")).is_err());
+
+        let clamped = ExpLinearInterpLookupTable::<F10, 10>::from_builder(
+            builder
+                .extrapolation(Extrapolation::Clamp)
+                .interpolation(Interpolation::Step),
+        )
+        .unwrap();
+        assert_eq!(clamped.evaluate(d("-20")), table.evaluate(d("-10")));
+        assert_eq!(clamped.evaluate(d("2.0005")), table.evaluate(d("2")));
+
+        assert!(matches!(
+            ExpLinearInterpLookupTable::<F10, 10>::from_builder(
+                LookupTableBuilder::new().end(d("1"))
+            ),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(
+            ExpLinearInterpLookupTable::<F10, 10>::from_builder(builder.step_size(d("-0.1")))
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_lookup_table_matches_sequential() {
+        let d = |s| FixedDecimal::<F10>::from_str(s).unwrap();
+        let sequential = LookupTable::new(
+            d("-10"),
+            d("10"),
+            d("0.00001"),
+            range_reduce_taylor_exp::<F10, 10>,
+        );
+        let parallel = LookupTable::new_parallel(
+            d("-10"),
+            d("10"),
+            d("0.00001"),
+            range_reduce_taylor_exp::<F10, 10>,
+        );
+        assert_eq!(parallel.table.len(), 2_000_000);
+        assert_eq!(parallel.table, sequential.table);
+    }
+
+    #[test]
+    fn test_lookup_table_exact_at_grid_points() {
+        let d = |s| FixedDecimal::<F10>::from_str(s).unwrap();
+        let table = ExpLinearInterpLookupTable::<F10, 10>::new(d("-3.7"), d("2.9"), d("0.0031"));
+        for (x, y) in table.lookup.iter() {
+            assert_eq!(table.evaluate(x), y);
+        }
+        for x in ["-3.7", "-3.6969", "-0.6", "-0.0017", "2.5"] {
+            let x = d(x);
+            assert_eq!(table.evaluate(x), range_reduce_taylor_exp::<F10, 10>(x));
+        }
+    }
+
+    #[test]
+    fn test_evaluate_with_slope() {
+        let d = |s| FixedDecimal::<F10>::from_str(s).unwrap();
+        let table = ExpLinearInterpLookupTable::<F10, 10>::new(d("-1"), d("1"), d("0.25"));
+        let samples = &table.lookup.table;
+        let segment_slope = (samples[5] - samples[4]) / d("0.25");
+        for x in ["0", "0.1", "0.2499999999"] {
+            let (value, slope) = table.evaluate_with_slope(d(x)).unwrap();
+            assert_eq!(value, table.evaluate(d(x)));
+            assert_eq!(slope, segment_slope);
+        }
+        let (value, slope) = table.evaluate_with_slope(d("0.25")).unwrap();
+        assert_eq!(value, samples[5]);
+        assert_eq!(slope, (samples[6] - samples[5]) / d("0.25"));
+        // past the last sample the value is held flat
+        assert_eq!(
+            table.evaluate_with_slope(d("0.9")).unwrap(),
+            (samples[7], FixedDecimal::zero())
+        );
+        assert!(table.evaluate_with_slope(d("1.5")).is_err());
+
+        let step = ExpLinearInterpLookupTable::<F10, 10>::from_builder(
+            LookupTableBuilder::new()
+                .start(d("-1"))
+                .end(d("1"))
+                .step_size(d("0.25"))
+                .interpolation(Interpolation::Step),
+        )
+        .unwrap();
+        assert_eq!(
+            step.evaluate_with_slope(d("0.1")).unwrap(),
+            (samples[4], FixedDecimal::zero())
+        );
+    }
+
+    #[test]
+    fn test_lookup_table_size() {
+        let d = |s| FixedDecimal::<F10>::from_str(s).unwrap();
+        for (start, end, step) in [
+            ("-10", "10", "0.001"),
+            ("-1", "1", "0.25"),
+            ("0", "1", "0.3"),
+            ("-2.5", "3.7", "0.0031"),
+        ] {
+            let (start, end, step) = (d(start), d(end), d(step));
+            let table = LookupTable::new(start, end, step, range_reduce_taylor_exp::<F10, 10>);
+            let len = LookupTable::estimate_len(start, end, step);
+            assert_eq!(len, table.table.len());
+            assert_eq!(table.memory_bytes(), table.table.capacity() * 16);
+            assert!(table.memory_bytes() >= len * 16);
+        }
+        const LEN: usize = LookupTable::<F10>::estimate_len(
+            FixedDecimal::from_scaled(0, 0, 0),
+            FixedDecimal::from_scaled(40, 0, 0),
+            FixedDecimal::from_scaled(0, 1, 5),
+        );
+        assert_eq!(LEN, 4_000_000);
+        assert_eq!(LookupTable::estimate_len(d("1"), d("-1"), d("0.1")), 0);
+        assert_eq!(LookupTable::estimate_len(d("0"), d("1"), d("0")), 0);
+    }
+
+    #[test]
+    fn test_lookup_table_try_new() {
+        let d = |s| FixedDecimal::<F10>::from_str(s).unwrap();
+        let exp = range_reduce_taylor_exp::<F10, 10>;
+        let table = LookupTable::try_new(d("-1"), d("1"), d("0.001"), 2_000, exp).unwrap();
+        assert_eq!(
+            table.table,
+            LookupTable::new(d("-1"), d("1"), d("0.001"), exp).table
+        );
+        assert!(matches!(
+            LookupTable::try_new(d("-1000"), d("1000"), d("0.0000000001"), 1_000_000, exp),
+            Err(FixedFastError::TableTooLarge(20_000_000_000_000))
+        ));
+        assert!(matches!(
+            LookupTable::try_new(d("-1"), d("1"), d("0.001"), 1_999, exp),
+            Err(FixedFastError::TableTooLarge(2_000))
+        ));
+        assert!(matches!(
+            LookupTable::try_new(d("-1"), d("1"), d("0"), 1_000, exp),
+            Err(FixedFastError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_lookup_table_iter() {
+        let table = ExpLinearInterpLookupTable::<F10, 10>::new(
+            FixedDecimal::<F10>::from_str("-1").unwrap(),
+            FixedDecimal::<F10>::from_str("1").unwrap(),
+            FixedDecimal::<F10>::from_str("0.25").unwrap(),
+        );
+        let pairs: Vec<_> = table.lookup.iter().collect();
+        assert_eq!(pairs.len(), table.lookup.table.len());
+        let (x, y) = pairs[0];
+        assert_eq!(x, FixedDecimal::<F10>::from_str("-1").unwrap());
+        assert_eq!(y, range_reduce_taylor_exp::<F10, 10>(x));
+        let (x, y) = pairs[pairs.len() - 1];
+        assert_eq!(x, FixedDecimal::<F10>::from_str("0.75").unwrap());
+        assert_eq!(y, range_reduce_taylor_exp::<F10, 10>(x));
+    }
 }
@@ -0,0 +1,223 @@
+use crate::{
+    error::{FixedFastError, Result},
+    exp::range_reduce_taylor_exp_try,
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    ln::range_reduce_arctanh_ln_try,
+};
+
+/// Discrete discount factor `1 / (1 + rate)^periods`. A rate close to -1
+/// over many periods fails with `Overflow`.
+pub fn discount_factor<T: FixedPrecision>(
+    rate: FixedDecimal<T>,
+    periods: u32,
+) -> Result<FixedDecimal<T>> {
+    validate_rate(rate)?;
+    (FixedDecimal::one() + rate).checked_pow(-(periods as i128))
+}
+
+/// Continuously compounded discount factor `e^(-rate * time)`, failing with
+/// `Overflow` when a negative rate makes it too large for `T`.
+pub fn continuous_discount<T: FixedPrecision>(
+    rate: FixedDecimal<T>,
+    time: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    validate_rate(rate)?;
+    range_reduce_taylor_exp_try::<T, 20>(-rate.checked_mul(time)?)
+}
+
+/// Future value `principal * (1 + rate)^periods` under discrete compounding.
+pub fn compound<T: FixedPrecision>(
+    principal: FixedDecimal<T>,
+    rate: FixedDecimal<T>,
+    periods: u32,
+) -> Result<FixedDecimal<T>> {
+    validate_rate(rate)?;
+    principal.checked_mul((FixedDecimal::one() + rate).checked_pow(periods as i128)?)
+}
+
+/// Present value of `periods` level payments made at the end of each
+/// period, `payment * (1 - (1 + rate)^-periods) / rate`, or
+/// `payment * periods` when the rate is zero.
+pub fn annuity_pv<T: FixedPrecision>(
+    payment: FixedDecimal<T>,
+    rate: FixedDecimal<T>,
+    periods: u32,
+) -> Result<FixedDecimal<T>> {
+    validate_rate(rate)?;
+    if rate == 0 {
+        return payment.checked_mul(FixedDecimal::from_i128(periods as i128));
+    }
+    let discount = (FixedDecimal::one() + rate).checked_pow(-(periods as i128))?;
+    payment
+        .checked_mul(FixedDecimal::one() - discount)?
+        .checked_div(rate)
+}
+
+/// Simple return `(to - from) / from`. Fails with `DivideByZero` when
+/// `from` is zero.
+pub fn pct_change<T: FixedPrecision>(
+    from: FixedDecimal<T>,
+    to: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    to.checked_sub(from)?.checked_div(from)
+}
+
+/// Continuously compounded return `ln(to / from)`, taken as a difference
+/// of logs so the ratio is never rounded. Both prices must be positive.
+pub fn log_return<T: FixedPrecision>(
+    from: FixedDecimal<T>,
+    to: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    if from <= 0 || to <= 0 {
+        return Err(FixedFastError::DomainError(
+            "log return needs positive prices",
+        ));
+    }
+    range_reduce_arctanh_ln_try::<T, 20>(to)?
+        .checked_sub(range_reduce_arctanh_ln_try::<T, 20>(from)?)
+}
+
+fn validate_rate<T: FixedPrecision>(rate: FixedDecimal<T>) -> Result<()> {
+    if rate <= -1 {
+        return Err(FixedFastError::DomainError(
+            "interest rate must be greater than -1",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    fn d(s: &str) -> FixedDecimal<F18> {
+        FixedDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_discount_factor() {
+        let tolerance = d("0.000000000001");
+        let cases = [
+            ("0.05", 10, "0.6139132535407591"),
+            ("0.1", 1, "0.9090909090909091"),
+            ("0.03", 30, "0.41198675951590635"),
+            ("-0.5", 1, "2"),
+        ];
+        for (rate, periods, expected) in cases {
+            let factor = discount_factor(d(rate), periods).unwrap();
+            assert!((factor - d(expected)).abs() < tolerance);
+        }
+        assert_eq!(discount_factor(d("0.07"), 0).unwrap(), FixedDecimal::one());
+        assert!(matches!(
+            discount_factor(d("-1"), 3),
+            Err(FixedFastError::DomainError(_))
+        ));
+        // 1.05^2000 overflows, but the factor itself is below one ulp
+        assert_eq!(
+            discount_factor(d("0.05"), 2000).unwrap(),
+            FixedDecimal::zero()
+        );
+        // 0.01^100 truncates to zero, the factor 1e200 overflows
+        assert!(matches!(
+            discount_factor(d("-0.99"), 100),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_compound() {
+        let value = compound(d("1000"), d("0.05"), 10).unwrap();
+        assert!((value - d("1628.894626777442")).abs() < d("0.000000001"));
+        assert_eq!(compound(d("250"), d("0.1"), 2).unwrap(), d("302.5"));
+        assert_eq!(compound(d("250"), d("0.1"), 0).unwrap(), d("250"));
+        assert!(compound(d("250"), d("-2"), 2).is_err());
+        // 2^80 is past the F18 range of about 1.7e20
+        assert!(matches!(
+            compound(d("1"), d("1"), 80),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(matches!(
+            compound(d("1000000"), d("0.5"), 100),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_annuity_pv() {
+        let tolerance = d("0.00000001");
+        let value = annuity_pv(d("100"), d("0.08"), 10).unwrap();
+        assert!((value - d("671.0081398941447")).abs() < tolerance);
+        let value = annuity_pv(d("500"), d("0.05"), 20).unwrap();
+        assert!((value - d("6231.105171269996")).abs() < tolerance);
+        assert_eq!(
+            annuity_pv(d("100"), FixedDecimal::zero(), 12).unwrap(),
+            d("1200")
+        );
+        assert_eq!(
+            annuity_pv(d("100"), d("0.08"), 0).unwrap(),
+            FixedDecimal::zero()
+        );
+        assert!(annuity_pv(d("100"), d("-1"), 5).is_err());
+        // the discount underflows to zero, leaving payment / rate
+        assert_eq!(annuity_pv(d("100"), d("1"), 200).unwrap(), d("100"));
+        assert!(matches!(
+            annuity_pv(d("100"), d("-0.99"), 100),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_continuous_discount() {
+        let tolerance = d("0.000000000001");
+        let factor = continuous_discount(d("0.05"), d("10")).unwrap();
+        assert!((factor - d("0.6065306597126334")).abs() < tolerance);
+        let factor = continuous_discount(d("0.04"), d("2.5")).unwrap();
+        assert!((factor - d("0.9048374180359595")).abs() < tolerance);
+        assert_eq!(
+            continuous_discount(d("0.05"), FixedDecimal::zero()).unwrap(),
+            FixedDecimal::one()
+        );
+        assert!(continuous_discount(d("-1.5"), d("1")).is_err());
+        assert!(matches!(
+            continuous_discount(d("-0.5"), d("200")),
+            Err(FixedFastError::Overflow)
+        ));
+        assert_eq!(
+            continuous_discount(d("0.5"), d("200")).unwrap(),
+            FixedDecimal::zero()
+        );
+    }
+
+    #[test]
+    fn test_returns() {
+        assert_eq!(pct_change(d("80"), d("100")).unwrap(), d("0.25"));
+        assert_eq!(pct_change(d("100"), d("80")).unwrap(), d("-0.2"));
+        assert_eq!(pct_change(d("-50"), d("-25")).unwrap(), d("-0.5"));
+        assert!(matches!(
+            pct_change(FixedDecimal::zero(), d("1")),
+            Err(FixedFastError::DivideByZero)
+        ));
+
+        let tolerance = d("0.000000000001");
+        let value = log_return(d("80"), d("100")).unwrap();
+        assert!((value - d("0.22314355131420976")).abs() < tolerance);
+        let value = log_return(d("100"), d("80")).unwrap();
+        assert!((value + d("0.22314355131420976")).abs() < tolerance);
+        assert_eq!(
+            log_return(d("42.5"), d("42.5")).unwrap(),
+            FixedDecimal::zero()
+        );
+        assert!(matches!(
+            log_return(FixedDecimal::zero(), d("1")),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(log_return(d("1"), d("-1")).is_err());
+    }
+}
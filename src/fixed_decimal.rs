@@ -1,6 +1,7 @@
 use crate::{
     error::{FixedFastError, Result as CrateResult},
-    sqrt::sqrt_newton_raphson_try,
+    sqrt::{sqrt_newton_raphson, sqrt_newton_raphson_try},
+    wide::{I256, mul_div},
 };
 use core::fmt;
 use serde::{Deserialize, Serialize};
@@ -15,9 +16,29 @@ use std::{
 
 pub trait FixedPrecision: Copy + Eq {
     const PRECISION: u32;
+
+    /// Longest string `Deserialize` will try to parse; longer input is
+    /// rejected before parsing. The default leaves room for every `i128`
+    /// value with sign and point, plus some trailing digits.
+    const MAX_STR_LEN: usize = 64;
 }
 
+/// Precision given as a const generic, so no marker struct has to be
+/// declared: `ConstPrecision<9>` behaves like a hand-written `F9`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ConstPrecision<const P: u32>;
+
+impl<const P: u32> FixedPrecision for ConstPrecision<P> {
+    const PRECISION: u32 = P;
+}
+
+/// `FixedDecimal` with its precision written inline, e.g. `FixedDecimalC<9>`.
+/// It is the same type as `FixedDecimal<ConstPrecision<P>>`, so every method,
+/// operator and function in the crate applies unchanged.
+pub type FixedDecimalC<const P: u32> = FixedDecimal<ConstPrecision<P>>;
+
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[repr(transparent)]
 pub struct FixedDecimal<T: FixedPrecision>(i128, std::marker::PhantomData<T>);
 
 const fn scale_raw(raw: i128, scale_index: i32) -> i128 {
@@ -31,6 +52,14 @@ const fn scale_raw(raw: i128, scale_index: i32) -> i128 {
 }
 
 impl<T: FixedPrecision> FixedDecimal<T> {
+    /// Number of fractional decimal digits, i.e. `T::PRECISION`. The raw
+    /// value is scaled by `10^DECIMAL_DIGITS`.
+    pub const DECIMAL_DIGITS: u32 = T::PRECISION;
+
+    pub const fn precision() -> u32 {
+        Self::DECIMAL_DIGITS
+    }
+
     pub const fn scale() -> i128 {
         10i128.pow(T::PRECISION)
     }
@@ -50,6 +79,17 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         Self(scale_raw(ln2_raw, scale_decimals), std::marker::PhantomData)
     }
 
+    /// `log2(e)`, the reciprocal of `ln2`.
+    pub const fn log2_e() -> Self {
+        let log2_e_raw = 1442695040888963407359924681001;
+        let log2_e_raw_length = 30;
+        let scale_decimals = T::PRECISION as i32 - log2_e_raw_length;
+        Self(
+            scale_raw(log2_e_raw, scale_decimals),
+            std::marker::PhantomData,
+        )
+    }
+
     pub const fn e() -> Self {
         let e_raw = 2718281828459045235360287471352;
         let e_raw_length = 30;
@@ -64,6 +104,38 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         Self(scale_raw(pi_raw, scale_decimals), std::marker::PhantomData)
     }
 
+    /// `1/√(2π)`, the normalization of the standard normal density.
+    pub const fn inv_sqrt_2pi() -> Self {
+        let inv_sqrt_2pi_raw = 398942280401432677939946059934;
+        let inv_sqrt_2pi_raw_length = 30;
+        let scale_decimals = T::PRECISION as i32 - inv_sqrt_2pi_raw_length;
+        Self(
+            scale_raw(inv_sqrt_2pi_raw, scale_decimals),
+            std::marker::PhantomData,
+        )
+    }
+
+    pub const fn sqrt_2pi() -> Self {
+        let sqrt_2pi_raw = 2506628274631000502415765284811;
+        let sqrt_2pi_raw_length = 30;
+        let scale_decimals = T::PRECISION as i32 - sqrt_2pi_raw_length;
+        Self(
+            scale_raw(sqrt_2pi_raw, scale_decimals),
+            std::marker::PhantomData,
+        )
+    }
+
+    /// The Euler–Mascheroni constant `γ`.
+    pub const fn euler_gamma() -> Self {
+        let euler_gamma_raw = 577215664901532860606512090082;
+        let euler_gamma_raw_length = 30;
+        let scale_decimals = T::PRECISION as i32 - euler_gamma_raw_length;
+        Self(
+            scale_raw(euler_gamma_raw, scale_decimals),
+            std::marker::PhantomData,
+        )
+    }
+
     pub fn two_pow_k(k: i32) -> Self {
         if k > 0 {
             FixedDecimal::one() << k
@@ -74,6 +146,23 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         }
     }
 
+    /// `2^k` that fails with `Overflow` when the result does not fit and with
+    /// a `DomainError` when it is too small to represent and would be zero.
+    pub fn checked_two_pow_k(k: i32) -> CrateResult<Self> {
+        let one = Self::scale();
+        if k >= 0 {
+            if k >= one.leading_zeros() as i32 {
+                return Err(FixedFastError::Overflow);
+            }
+            Ok(Self::from_raw(one << k))
+        } else {
+            match one.checked_shr(k.unsigned_abs()) {
+                Some(raw) if raw != 0 => Ok(Self::from_raw(raw)),
+                _ => Err(FixedFastError::DomainError("2^k underflows to zero")),
+            }
+        }
+    }
+
     pub fn to_le_bytes(self) -> [u8; 16] {
         self.0.to_le_bytes()
     }
@@ -82,22 +171,142 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         Self::from_raw(i128::from_le_bytes(bytes))
     }
 
+    /// Raw value as 32 zero-padded lowercase hex digits (two's complement).
+    /// Intended for inspecting wire dumps, independent of precision.
+    pub fn to_hex(&self) -> String {
+        format!("{:032x}", self.0 as u128)
+    }
+
+    /// Parses the output of `to_hex`. The input must be exactly 32 hex digits.
+    pub fn from_hex(x: &str) -> CrateResult<Self> {
+        if x.len() != 32 {
+            return Err(FixedFastError::DomainError("hex value must be 32 digits"));
+        }
+        // `from_str_radix` would also take a leading `+`
+        if !x.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(FixedFastError::DomainError("invalid hex digit"));
+        }
+        let raw = u128::from_str_radix(x, 16)
+            .map_err(|_| FixedFastError::DomainError("invalid hex digit"))?;
+        Ok(Self::from_raw(raw as i128))
+    }
+
+    /// Raw scaled value narrowed to an `i64` wire field, failing with
+    /// `Overflow` if it does not fit.
+    pub fn checked_to_raw_i64(&self) -> CrateResult<i64> {
+        i64::try_from(self.0).map_err(|_| FixedFastError::Overflow)
+    }
+
+    /// Raw scaled value narrowed to an `i64`, clamped to `i64::MIN` or
+    /// `i64::MAX` instead of wrapping.
+    pub fn to_raw_i64_saturating(&self) -> i64 {
+        self.0.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Widens a raw scaled `i64` read from the wire. Every `i64` fits, so
+    /// this cannot fail.
+    pub const fn from_raw_i64(raw: i64) -> Self {
+        Self::from_raw(raw as i128)
+    }
+
+    /// Whether the fractional part is zero.
+    pub fn is_integer(&self) -> bool {
+        self.0 % Self::scale() == 0
+    }
+
+    /// Largest integer not above `self`, so `-2.5` floors to `-3`.
     pub fn floor(self) -> Self {
-        Self::from_raw(self.0 / Self::scale() * Self::scale())
+        Self::from_raw(self.floor_i128() * Self::scale())
     }
 
     pub fn floor_i128(self) -> i128 {
-        self.0 / Self::scale()
+        self.0.div_euclid(Self::scale())
     }
 
+    /// Smallest integer not below `self`, so `-1.5` ceils to `-1`.
+    pub fn ceil(self) -> Self {
+        Self::from_raw(self.ceil_i128() * Self::scale())
+    }
+
+    pub fn ceil_i128(self) -> i128 {
+        let floor = self.floor_i128();
+        if self.0.rem_euclid(Self::scale()) == 0 {
+            floor
+        } else {
+            floor + 1
+        }
+    }
+
+    /// Integer part, rounding toward zero. The integer itself is `to_i128`.
+    pub fn trunc(self) -> Self {
+        Self::from_raw(self.to_i128() * Self::scale())
+    }
+
+    /// `(trunc, fraction)` with both parts carrying the sign of `self`, so
+    /// `-2.75` splits into `(-2, -0.75)` and the parts always add back up.
+    pub fn split(&self) -> (Self, Self) {
+        let scale = Self::scale();
+        (
+            Self::from_raw(self.0 / scale * scale),
+            Self::from_raw(self.0 % scale),
+        )
+    }
+
+    /// The `T::PRECISION` digits after the decimal point, most significant
+    /// first, trailing zeros included and without allocating. Digits are of
+    /// the magnitude, so `-0.125` yields the same as `0.125`; take the sign
+    /// from `signum`.
+    pub fn fractional_digits(&self) -> impl Iterator<Item = u8> {
+        let fraction = self.0.unsigned_abs() % Self::scale() as u128;
+        (0..T::PRECISION)
+            .rev()
+            .map(move |place| (fraction / 10u128.pow(place) % 10) as u8)
+    }
+
+    /// Nearest integer, with ties going to the even neighbour (`2.5` rounds
+    /// to `2`, `-2.5` to `-2`, `3.5` to `4`).
+    pub fn round(self) -> Self {
+        let scale = Self::scale();
+        let (floor, remainder) = (self.0.div_euclid(scale), self.0.rem_euclid(scale));
+        let twice = remainder * 2;
+        let rounded = if twice > scale || (twice == scale && floor % 2 != 0) {
+            floor + 1
+        } else {
+            floor
+        };
+        Self::from_raw(rounded * scale)
+    }
+
+    /// The integer `x`. Like plain `i128` arithmetic, scaling a value
+    /// beyond `i128::MAX / 10^PRECISION` panics in debug builds and wraps
+    /// in release builds; use `checked_from_i128` for untrusted input.
     pub fn from_i128(x: i128) -> Self {
         Self(x * Self::scale(), std::marker::PhantomData)
     }
 
+    /// The integer `x`, failing with `Overflow` when it is out of range.
+    pub fn checked_from_i128(x: i128) -> CrateResult<Self> {
+        x.checked_mul(Self::scale())
+            .map(Self::from_raw)
+            .ok_or(FixedFastError::Overflow)
+    }
+
     pub const fn from_raw(x: i128) -> Self {
         Self(x, std::marker::PhantomData)
     }
 
+    /// Builds `integer + fraction / 10^fraction_digits` in a `const`
+    /// context, truncating extra fraction digits toward zero like
+    /// `from_str`. The fraction carries the sign of the value, so `-1.25`
+    /// is `from_scaled(-1, -25, 2)` and `-0.05` is `from_scaled(0, -5, 2)`.
+    pub const fn from_scaled(integer: i128, fraction: i128, fraction_digits: u32) -> Self {
+        Self(
+            integer * Self::scale()
+                + scale_raw(fraction, T::PRECISION as i32 - fraction_digits as i32),
+            std::marker::PhantomData,
+        )
+    }
+
     pub const fn from_f64(x: f64) -> Self {
         Self((x * Self::scale() as f64) as i128, std::marker::PhantomData)
     }
@@ -106,6 +315,29 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         Self::from_raw(1)
     }
 
+    /// One unit in the last place, the spacing between adjacent values.
+    pub const fn ulp() -> Self {
+        Self::from_raw(1)
+    }
+
+    /// The next representable value above `self`, one ulp up. Stays at the
+    /// largest value instead of wrapping.
+    pub fn next_up(&self) -> Self {
+        Self::from_raw(self.0.saturating_add(1))
+    }
+
+    /// The next representable value below `self`, one ulp down. Stays at
+    /// the smallest value instead of wrapping.
+    pub fn next_down(&self) -> Self {
+        Self::from_raw(self.0.saturating_sub(1))
+    }
+
+    /// Number of ulps separating `self` and `other`, saturating at
+    /// `i128::MAX` for values at opposite ends of the range.
+    pub fn ulp_distance(&self, other: Self) -> i128 {
+        self.0.abs_diff(other.0).min(i128::MAX as u128) as i128
+    }
+
     pub fn signum(&self) -> i128 {
         if self.0 > 0 {
             1
@@ -116,13 +348,25 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         }
     }
 
-    pub fn from_str(x: &str) -> std::result::Result<Self, &'static str> {
+    /// Parses a decimal string, truncating fractional digits beyond
+    /// `T::PRECISION`. Malformed input is a `DomainError` and values that do
+    /// not fit the backing integer are an `Overflow`. Every spelling of
+    /// negative zero (`-0`, `-0.000`, or a negative value that truncates
+    /// away) parses to exactly `zero()`, which `Display` prints as `0`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(x: &str) -> CrateResult<Self> {
         let is_negative = x.starts_with('-');
-        let x = if is_negative { &x[1..] } else { x };
+        // strip one sign; `parse` below would accept a second one
+        let x = x.strip_prefix(['-', '+']).unwrap_or(x);
 
-        let parts: Vec<&str> = x.split('.').collect();
-        let integer_part = parts[0];
-        let decimal_part = parts.get(1).unwrap_or(&"0");
+        let (integer_part, decimal_part) = x.split_once('.').unwrap_or((x, "0"));
+        if !integer_part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(FixedFastError::DomainError("invalid integer part"));
+        }
+        // checked before truncating, which slices by bytes
+        if !decimal_part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(FixedFastError::DomainError("invalid decimal part"));
+        }
 
         let decimal_part = if decimal_part.len() > T::PRECISION as usize {
             &decimal_part[..T::PRECISION as usize]
@@ -130,35 +374,282 @@ impl<T: FixedPrecision> FixedDecimal<T> {
             decimal_part
         };
 
-        let mut result = Self::from_i128(
-            integer_part
-                .parse::<i128>()
-                .map_err(|_| "Invalid integer part")?,
-        );
+        let integer_value = integer_part
+            .parse::<i128>()
+            .map_err(|_| FixedFastError::DomainError("invalid integer part"))?;
 
         let scale = T::PRECISION as i32 - decimal_part.len() as i32;
         let mut decimal_value = decimal_part
             .parse::<i128>()
-            .map_err(|_| "Invalid decimal part")?;
+            .map_err(|_| FixedFastError::DomainError("invalid decimal part"))?;
         if scale > 0 {
             decimal_value *= 10i128.pow(scale as u32);
         } else if scale < 0 {
             decimal_value /= 10i128.pow(-scale as u32);
         }
 
-        result.0 += decimal_value;
+        // build negative values downwards so that i128::MIN is reachable
+        let magnitude = integer_value
+            .checked_mul(Self::scale())
+            .ok_or(FixedFastError::Overflow)?;
+        let raw = if is_negative {
+            magnitude
+                .checked_neg()
+                .and_then(|m| m.checked_sub(decimal_value))
+        } else {
+            magnitude.checked_add(decimal_value)
+        };
+
+        Ok(Self::from_raw(raw.ok_or(FixedFastError::Overflow)?))
+    }
+
+    /// Parses a percentage such as `"2.5%"` or `"-0.1 %"` into its fraction,
+    /// `0.025` and `-0.001`. The `%` is required, with optional whitespace
+    /// before it; digits that fall past `T::PRECISION` after dividing by 100
+    /// are truncated as in `from_str`.
+    pub fn from_percent_str(s: &str) -> CrateResult<Self> {
+        let number = s
+            .trim()
+            .strip_suffix('%')
+            .ok_or(FixedFastError::DomainError("percentage must end with '%'"))?;
+        Ok(Self::from_raw(Self::from_str(number.trim_end())?.0 / 100))
+    }
 
-        if is_negative {
-            result.0 = -result.0;
+    /// The value times 100 with a `%` appended, so `0.025` is `"2.5%"`. The
+    /// decimal point is moved in the digits rather than multiplying, so it
+    /// cannot overflow, and nothing is rounded.
+    pub fn to_percent_string(&self) -> String {
+        let digits = self.to_string();
+        let (sign, digits) = match digits.strip_prefix('-') {
+            Some(magnitude) => ("-", magnitude),
+            None => ("", digits.as_str()),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let fraction = format!("{fraction:0<2}");
+        let (moved, fraction) = fraction.split_at(2);
+        let integer = format!("{integer}{moved}");
+        let integer = match integer.trim_start_matches('0') {
+            "" => "0",
+            trimmed => trimmed,
+        };
+        if fraction.is_empty() {
+            format!("{sign}{integer}%")
+        } else {
+            format!("{sign}{integer}.{fraction}%")
         }
+    }
 
-        Ok(result)
+    /// `from_str` that fails with a `DomainError` instead of truncating
+    /// when `x` has more fractional digits than `T::PRECISION`, trailing
+    /// zeros included.
+    pub fn from_str_exact(x: &str) -> CrateResult<Self> {
+        if let Some((_, fraction)) = x.split_once('.')
+            && fraction.len() > T::PRECISION as usize
+        {
+            return Err(FixedFastError::DomainError(
+                "more fractional digits than the precision",
+            ));
+        }
+        Self::from_str(x)
+    }
+
+    /// `from_str` that also reports whether the value lost precision, i.e.
+    /// whether any nonzero fractional digit past `T::PRECISION` was
+    /// dropped. Extra trailing zeros lose nothing and are not flagged.
+    pub fn from_str_lossy(x: &str) -> CrateResult<(Self, bool)> {
+        let value = Self::from_str(x)?;
+        let lossy = x
+            .split_once('.')
+            .and_then(|(_, fraction)| fraction.get(T::PRECISION as usize..))
+            .is_some_and(|dropped| dropped.bytes().any(|b| b != b'0'));
+        Ok((value, lossy))
+    }
+
+    /// Whether `s` parses, at this precision, to exactly `self`, so `1.5`
+    /// equals both `"1.5"` and `"1.50"`. Digits past the precision are
+    /// truncated as in `from_str`; unparseable input is just unequal.
+    pub fn eq_str(&self, s: &str) -> bool {
+        Self::from_str(s).is_ok_and(|parsed| parsed == *self)
+    }
+
+    /// Parses a string whose integer part may contain `separator` between
+    /// digit groups, e.g. `1,234,567.89` with `','`.
+    pub fn from_str_grouped(x: &str, separator: char) -> CrateResult<Self> {
+        let (integer_part, decimal_part) = match x.find('.') {
+            Some(index) => x.split_at(index),
+            None => (x, ""),
+        };
+        let integer_part: String = integer_part.chars().filter(|c| *c != separator).collect();
+        Self::from_str(&(integer_part + decimal_part))
+    }
+
+    /// Formats like `Display` but inserts `separator` between every three
+    /// integer digits, e.g. `1,234,567.89` with `','`.
+    pub fn to_string_grouped(&self, separator: char) -> String {
+        let integer = (self.0 / Self::scale()).unsigned_abs();
+        let mut grouped = String::new();
+        if self.0 < 0 {
+            grouped.push('-');
+        }
+        push_grouped(&mut grouped, integer, Some(separator));
+        let decimal = self.0.unsigned_abs() % Self::scale() as u128;
+        if decimal != 0 {
+            let decimal_string = format!("{:0width$}", decimal, width = T::PRECISION as usize);
+            grouped.push('.');
+            grouped.push_str(decimal_string.trim_end_matches('0'));
+        }
+        grouped
     }
 
-    pub fn to_raw(&self) -> i128 {
+    /// Formats with every fractional digit, e.g. `1.500000000` at precision 9.
+    pub fn to_string_full(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// A `Display` view for money amounts such as `$1,234.50` or `-$5.00`:
+    /// `symbol` after the sign, exactly `decimals` fraction digits rounded
+    /// half to even like `round`, and the integer digits grouped by
+    /// `grouping` in threes when given.
+    pub fn currency<'a>(
+        &self,
+        symbol: &'a str,
+        decimals: u32,
+        grouping: Option<char>,
+    ) -> CurrencyFormat<'a, T> {
+        CurrencyFormat {
+            value: *self,
+            symbol,
+            decimals,
+            grouping,
+        }
+    }
+
+    /// Decimal string rounded half-even to exactly `dp` fractional digits,
+    /// the same as formatting with `{:.dp$}`. Plain `to_string` shows the
+    /// stored digits as they are, truncation included.
+    pub fn to_string_dp(&self, dp: u32) -> String {
+        format!("{:.*}", dp as usize, self)
+    }
+
+    /// Decimal string with trailing fractional zeros trimmed, but keeping at
+    /// least `min_dp` fractional digits, padding with zeros when the value
+    /// has fewer: `1` shows as `1.0` and `1.5` as `1.5` at `min_dp = 1`.
+    /// Nothing is rounded; every stored digit is kept.
+    pub fn to_string_min_dp(&self, min_dp: u32) -> String {
+        let full = format!("{:#}", self);
+        let (integer, fraction) = full.split_once('.').unwrap_or((&full, ""));
+        let fraction = fraction.trim_end_matches('0');
+        let width = fraction.len().max(min_dp as usize);
+        if width == 0 {
+            return integer.to_string();
+        }
+        format!("{integer}.{fraction:0<width$}")
+    }
+
+    /// Decimal string rounded half-even to `n` significant digits, trailing
+    /// zeros kept. Like C's `%g`, it switches to scientific notation (`1.23e4`)
+    /// when the leading digit's exponent is below -4 or at least `n`. Zero
+    /// is `"0"`, and `n` of zero is treated as one.
+    pub fn to_sig_figs(&self, n: u32) -> String {
+        if self.0 == 0 {
+            return "0".to_string();
+        }
+        let n = n.max(1);
+        let magnitude = self.0.unsigned_abs();
+        let len = magnitude.ilog10() + 1;
+        let mut exponent = len as i32 - 1 - T::PRECISION as i32;
+        let digits = match len.checked_sub(n) {
+            Some(dropped) if dropped > 0 => {
+                let divisor = 10u128.pow(dropped);
+                let (mut kept, remainder) = (magnitude / divisor, magnitude % divisor);
+                if remainder * 2 > divisor || (remainder * 2 == divisor && kept % 2 == 1) {
+                    kept += 1;
+                }
+                if kept == 10u128.pow(n) {
+                    kept /= 10;
+                    exponent += 1;
+                }
+                kept.to_string()
+            }
+            _ => format!("{:0<width$}", magnitude, width = n as usize),
+        };
+        let sign = if self.0 < 0 { "-" } else { "" };
+        if exponent < -4 || exponent >= n as i32 {
+            let (lead, rest) = digits.split_at(1);
+            let point = if rest.is_empty() { "" } else { "." };
+            format!("{sign}{lead}{point}{rest}e{exponent}")
+        } else if exponent < 0 {
+            let zeros = "0".repeat((-exponent - 1) as usize);
+            format!("{sign}0.{zeros}{digits}")
+        } else {
+            let (integer, fraction) = digits.split_at(exponent as usize + 1);
+            let point = if fraction.is_empty() { "" } else { "." };
+            format!("{sign}{integer}{point}{fraction}")
+        }
+    }
+
+    pub const fn to_raw(&self) -> i128 {
+        self.0
+    }
+
+    /// Value given in scaled units of `10^-PRECISION`, so `150` at F2 is
+    /// `1.50`. The same as `from_raw`; whole units go through `from_i128`.
+    pub const fn from_scaled_units(units: i128) -> Self {
+        Self::from_raw(units)
+    }
+
+    /// The value in scaled units of `10^-PRECISION`, the inverse of
+    /// `from_scaled_units` and the same as `to_raw`. Whole units, truncated,
+    /// come from `to_i128`.
+    pub const fn scaled_units(&self) -> i128 {
         self.0
     }
 
+    /// Key for sorting or bucketing by value. It is the raw value, whose
+    /// integer order is exactly the `Ord` order of `FixedDecimal<T>`, so
+    /// `sort_by_key(|x| x.sort_key())` gives the same result as `sort()`.
+    /// Keys are only comparable between values of the same precision.
+    pub const fn sort_key(&self) -> i128 {
+        self.0
+    }
+
+    /// Precision-independent key for hashing or bucketing: the raw value with
+    /// trailing zeros stripped, paired with the number of fractional digits
+    /// left. Equal numbers give equal keys at any precision, e.g. `1.5` is
+    /// `(15, 1)` at both F9 and F18, and zero is `(0, 0)`. Unlike
+    /// `sort_key`, the key does not order values.
+    pub const fn canonical_key(&self) -> (i128, u32) {
+        let (mut mantissa, mut digits) = (self.0, T::PRECISION);
+        while digits > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            digits -= 1;
+        }
+        (mantissa, digits)
+    }
+
+    /// Estimate of the significant decimal digits the value still carries:
+    /// the digit count of the raw magnitude, zero for zero. Every digit
+    /// lost to a truncating divide shows up here, so `1e-15` at F18 has 4
+    /// digits left whatever it was computed from. A diagnostic for
+    /// precision collapse; see `PrecisionGuard`.
+    pub const fn debug_significant_digits(&self) -> u32 {
+        match self.0.unsigned_abs().checked_ilog10() {
+            Some(log) => log + 1,
+            None => 0,
+        }
+    }
+
+    /// Number of significant bits in the absolute raw value; zero for zero.
+    pub const fn raw_bits(&self) -> u32 {
+        i128::BITS - self.leading_zeros()
+    }
+
+    /// Leading zero bits of the absolute raw value, i.e. the headroom left
+    /// before the magnitude reaches the width of the backing integer.
+    pub const fn leading_zeros(&self) -> u32 {
+        self.0.unsigned_abs().leading_zeros()
+    }
+
     pub fn to_i128(&self) -> i128 {
         self.0 / Self::scale()
     }
@@ -167,6 +658,13 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         self.0 as f64 / Self::scale() as f64
     }
 
+    /// Whether `to_f64()` lies within `tol` of `other`. Meant for checking
+    /// results against float references; see the `f64` comparison impls
+    /// for why an exact match is rarely what you want.
+    pub fn approx_eq_f64(&self, other: f64, tol: f64) -> bool {
+        (self.to_f64() - other).abs() <= tol
+    }
+
     pub fn neg(&self) -> Self {
         Self::from_raw(-self.0)
     }
@@ -203,7 +701,22 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         Self::from_raw(self.0 / right)
     }
 
+    /// Integer power by repeated multiplication. A negative `power` goes
+    /// through `checked_pow` instead, which squares rather than looping
+    /// `|power|` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero and `power` is negative, or if a negative
+    /// power overflows.
     pub fn pow_i128(&self, power: i128) -> Self {
+        if power < 0 {
+            return match self.checked_pow(power) {
+                Ok(result) => result,
+                Err(FixedFastError::DivideByZero) => panic!("zero to a negative power"),
+                Err(error) => panic!("pow_i128 failed: {error}"),
+            };
+        }
         let mut result = Self::one();
         for _ in 0..power {
             result = result * self.0 / Self::scale();
@@ -211,16 +724,145 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         result
     }
 
-    pub fn polynomial(&self, coefficients: &[Self]) -> Self {
-        let mut result = coefficients[0];
-        let mut x_n = *self;
-        for coefficient in coefficients[1..].iter() {
-            result += *coefficient * x_n;
-            x_n *= *self;
+    /// `self^power` by exponentiation by squaring, failing with `Overflow`
+    /// instead of panicking. Each product truncates once, so results can
+    /// differ from `pow_i128` in the last digits. Zero to a negative power
+    /// is `DivideByZero`.
+    pub fn checked_pow(self, power: i128) -> CrateResult<Self> {
+        let magnitude = power.unsigned_abs();
+        if power >= 0 {
+            return self.checked_pow_unsigned(magnitude);
+        }
+        // a base of at least one keeps its positive power precise, so only
+        // the final reciprocal truncates
+        if self.0.unsigned_abs() >= Self::scale().unsigned_abs()
+            && let Ok(positive) = self.checked_pow_unsigned(magnitude)
+        {
+            return Self::one().checked_div(positive);
+        }
+        // otherwise the positive power could truncate to zero or overflow
+        // while its reciprocal is representable, so raise the reciprocal
+        Self::one()
+            .checked_div(self)?
+            .checked_pow_unsigned(magnitude)
+    }
+
+    fn checked_pow_unsigned(self, power: u128) -> CrateResult<Self> {
+        let (mut result, mut base, mut power) = (Self::one(), self, power);
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            power >>= 1;
+            if power > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Exact `base^exp` for an integer base. The power is taken by
+    /// squaring on the unscaled integer and scaled once at the end, so the
+    /// result is exact or fails with `Overflow`. `0^0` is one.
+    pub fn ipow(base: i128, exp: u32) -> CrateResult<Self> {
+        base.checked_pow(exp)
+            .and_then(|power| power.checked_mul(Self::scale()))
+            .map(Self::from_raw)
+            .ok_or(FixedFastError::Overflow)
+    }
+
+    /// `self^power` by exponentiation by squaring, clamping to the largest
+    /// or smallest representable value when it overflows. Each product
+    /// truncates once, so results can differ from `pow_i128` in the last
+    /// digits.
+    pub fn saturating_pow(self, power: u32) -> Self {
+        let multiply = |a: Self, b: Self| match mul_div(a.0, b.0, Self::scale()) {
+            Some(raw) => Self::from_raw(raw),
+            None if (a.0 < 0) == (b.0 < 0) => Self::from_raw(i128::MAX),
+            None => Self::from_raw(i128::MIN),
+        };
+        let (mut result, mut base, mut power) = (Self::one(), self, power);
+        while power > 0 {
+            if power & 1 == 1 {
+                result = multiply(result, base);
+            }
+            power >>= 1;
+            if power > 0 {
+                base = multiply(base, base);
+            }
         }
         result
     }
 
+    /// Evaluates `c0 + c1*x + c2*x^2 + ...` using Horner's method, which
+    /// never forms the raw powers of `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coefficients` is empty.
+    pub fn polynomial(&self, coefficients: &[Self]) -> Self {
+        let (highest, rest) = coefficients
+            .split_last()
+            .expect("polynomial needs at least one coefficient");
+        rest.iter().rev().fold(*highest, |result, coefficient| {
+            result * *self + *coefficient
+        })
+    }
+
+    /// Linear interpolation `self + (other - self) * t`. `t` is not clamped,
+    /// so values outside `[0, 1]` extrapolate along the same line.
+    pub fn lerp(self, other: Self, t: Self) -> Self {
+        self + (other - self) * t
+    }
+
+    /// One step of an exponential moving average, `self + alpha * (x -
+    /// self)`, with `self` as the current state and `alpha` the smoothing
+    /// factor in `[0, 1]`. The difference, product and sum are formed in
+    /// 256 bits and the product is truncated once, so the step neither
+    /// overflows at F18 nor truncates twice, even when `x - self` does not
+    /// fit in `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in `T`, which needs `alpha`
+    /// outside `[0, 1]`.
+    pub fn ema_step(self, x: Self, alpha: Self) -> Self {
+        let state = I256::from_i128(self.0);
+        I256::from_i128(x.0)
+            .checked_sub(state)
+            .and_then(|difference| {
+                difference.checked_mul_div(I256::from_i128(alpha.0), I256::from_i128(Self::scale()))
+            })
+            .and_then(|step| step.checked_add(state))
+            .and_then(I256::to_i128)
+            .map(Self::from_raw)
+            .expect("ema_step overflowed")
+    }
+
+    /// `sqrt(self^2 + other^2)` without forming the squares, which overflow
+    /// at F18 for inputs above about 13. The smaller magnitude is divided by
+    /// the larger, so only `1 + ratio^2 <= 2` goes through the root. The
+    /// root's truncation error is scaled by the larger input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in `T`.
+    pub fn hypot(self, other: Self) -> Self {
+        let (a, b) = (self.abs(), other.abs());
+        let (large, small) = if a >= b { (a, b) } else { (b, a) };
+        if large.0 == 0 {
+            return Self::zero();
+        }
+        // the ratio is at most one, but its plain square still multiplies
+        // two raw values near `scale`, which overflows from F20 on
+        let ratio = Self::from_raw(
+            mul_div(small.0, Self::scale(), large.0).expect("hypot ratio exceeds one"),
+        );
+        let ratio_squared = ratio.checked_squared().expect("hypot ratio exceeds one");
+        let root = sqrt_newton_raphson::<T, 20>(Self::one() + ratio_squared);
+        Self::from_raw(mul_div(large.0, root.0, Self::scale()).expect("hypot overflowed"))
+    }
+
     pub fn squared(&self) -> Self {
         Self::from_raw(self.0 * self.0 / Self::scale())
     }
@@ -235,29 +877,69 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         )
     }
 
+    /// `squared` through the 256-bit multiply, failing with `Overflow`
+    /// rather than wrapping. The plain version already overflows at F18
+    /// for inputs above about 13.
+    pub fn checked_squared(&self) -> CrateResult<Self> {
+        self.checked_mul(*self)
+    }
+
+    /// `cubed` through the 256-bit multiply, failing with `Overflow`.
+    pub fn checked_cubed(&self) -> CrateResult<Self> {
+        self.checked_squared()?.checked_mul(*self)
+    }
+
+    /// `tesseracted` through the 256-bit multiply, failing with `Overflow`.
+    pub fn checked_tesseracted(&self) -> CrateResult<Self> {
+        self.checked_cubed()?.checked_mul(*self)
+    }
+
     pub fn abs(&self) -> Self {
         Self::from_raw(self.0.abs())
     }
 
-    pub fn to_string(&self) -> String {
-        let decimal = self.0.abs() % Self::scale();
-        let decimal_string = format!("{:0width$}", decimal, width = T::PRECISION as usize);
-        let decimal_str = decimal_string.trim_end_matches('0');
+    /// `|self - other|`, saturating at the maximum when the distance does
+    /// not fit. The distance is formed unsigned, so it never overflows on
+    /// the way to the check; `(self - other).abs()` can overflow in either
+    /// step.
+    pub fn abs_diff(self, other: Self) -> Self {
+        self.checked_abs_diff(other)
+            .unwrap_or(Self::from_raw(i128::MAX))
+    }
 
-        if decimal_str.is_empty() {
-            format!("{}", self.to_i128())
-        } else {
-            format!("{}.{}", self.to_i128(), decimal_str)
-        }
+    /// `abs_diff` that fails with `Overflow` instead of saturating.
+    pub fn checked_abs_diff(self, other: Self) -> CrateResult<Self> {
+        i128::try_from(self.0.abs_diff(other.0))
+            .map(Self::from_raw)
+            .map_err(|_| FixedFastError::Overflow)
+    }
+
+    /// `self` if positive, otherwise zero.
+    pub fn clamp_positive(&self) -> Self {
+        Self::from_raw(self.0.max(0))
+    }
+
+    /// `self` if negative, otherwise zero.
+    pub fn clamp_negative(&self) -> Self {
+        Self::from_raw(self.0.min(0))
+    }
+
+    /// Floors `self` at zero, the same as `clamp_positive`. Reads better
+    /// for balances and quantities that cannot go negative.
+    pub fn saturating_to_zero(&self) -> Self {
+        self.clamp_positive()
     }
 
-    /// Checked division that returns an error when dividing by zero.
+    /// Checked division that returns an error when dividing by zero, or
+    /// `Overflow` when the quotient does not fit. The scaled dividend is
+    /// formed in 256 bits, so large dividends do not overflow on the way.
     pub fn checked_div(self, rhs: Self) -> CrateResult<Self> {
         if rhs.0 == 0 {
-            Err(FixedFastError::DivideByZero)
-        } else {
-            Ok(self.div(rhs))
+            return Err(FixedFastError::DivideByZero);
         }
+        mul_div(self.0, Self::scale(), rhs.0)
+            .map(Self::from_raw)
+            .ok_or(FixedFastError::Overflow)
     }
 
     /// Square root with error handling. Uses Newton-Raphson with compile-time depth.
@@ -273,6 +955,35 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         }
     }
 
+    /// Left shift of the raw value that fails with `Overflow` when any
+    /// significant bit (or the sign) would be lost, and with a `DomainError`
+    /// for shifts of 128 or more.
+    pub fn checked_shl(self, rhs: u32) -> CrateResult<Self> {
+        if rhs >= i128::BITS {
+            return Err(FixedFastError::DomainError("shift amount exceeds 127"));
+        }
+        let shifted = self.0 << rhs;
+        if shifted >> rhs != self.0 {
+            return Err(FixedFastError::Overflow);
+        }
+        Ok(Self::from_raw(shifted))
+    }
+
+    /// Arithmetic right shift of the raw value that fails with a
+    /// `DomainError` for shifts of 128 or more.
+    pub fn checked_shr(self, rhs: u32) -> CrateResult<Self> {
+        self.0
+            .checked_shr(rhs)
+            .map(Self::from_raw)
+            .ok_or(FixedFastError::DomainError("shift amount exceeds 127"))
+    }
+
+    /// Sums an iterator with `checked_add`, failing with `Overflow` on the
+    /// first step that overflows rather than wrapping like `Sum`.
+    pub fn checked_sum<I: Iterator<Item = Self>>(mut iter: I) -> CrateResult<Self> {
+        iter.try_fold(Self::zero(), Self::checked_add)
+    }
+
     /// Checked subtraction detecting overflow.
     pub fn checked_sub(self, rhs: Self) -> CrateResult<Self> {
         match self.0.checked_sub(rhs.0) {
@@ -281,24 +992,125 @@ impl<T: FixedPrecision> FixedDecimal<T> {
         }
     }
 
-    /// Checked multiplication detecting overflow.
+    /// Checked multiplication detecting overflow. The raw product is formed
+    /// in 256 bits, so only a result that does not fit fails.
     pub fn checked_mul(self, rhs: Self) -> CrateResult<Self> {
-        match self.0.checked_mul(rhs.0) {
-            Some(prod_raw) => Ok(Self::from_raw(prod_raw / Self::scale())),
-            None => Err(FixedFastError::Overflow),
-        }
+        mul_div(self.0, rhs.0, Self::scale())
+            .map(Self::from_raw)
+            .ok_or(FixedFastError::Overflow)
     }
 }
 
+/// Trailing fractional zeros are trimmed; the alternate flag (`{:#}`) keeps
+/// all `T::PRECISION` digits instead. A precision such as `{:.2}` rounds
+/// half to even to that many places. Width, fill, alignment, `+` and
+/// sign-aware zero padding (`{:08.2}`) behave as for the std numeric types.
+/// Values that round to zero print without a minus sign.
 impl<T: FixedPrecision> fmt::Display for FixedDecimal<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        let magnitude = self.0.unsigned_abs();
+        let (integer, decimal_string) = match f.precision() {
+            Some(decimals) => rounded_digits::<T>(magnitude, decimals as u32),
+            None => {
+                let scale = Self::scale() as u128;
+                let decimal_string = format!(
+                    "{:0width$}",
+                    magnitude % scale,
+                    width = T::PRECISION as usize
+                );
+                let decimal_string = if f.alternate() {
+                    decimal_string
+                } else {
+                    decimal_string.trim_end_matches('0').to_string()
+                };
+                (magnitude / scale, decimal_string)
+            }
+        };
+        let digits = if decimal_string.is_empty() {
+            integer.to_string()
+        } else {
+            format!("{}.{}", integer, decimal_string)
+        };
+        let is_zero = integer == 0 && decimal_string.bytes().all(|b| b == b'0');
+        f.pad_integral(self.0 >= 0 || is_zero, "", &digits)
+    }
+}
+
+fn push_grouped(out: &mut String, integer: u128, separator: Option<char>) {
+    let digits = integer.to_string();
+    for (i, digit) in digits.chars().enumerate() {
+        if let Some(separator) = separator
+            && i > 0
+            && (digits.len() - i).is_multiple_of(3)
+        {
+            out.push(separator);
+        }
+        out.push(digit);
+    }
+}
+
+// (integer, fraction digits) of a raw magnitude at `decimals` places,
+// rounded half to even when digits are dropped and zero padded when there
+// are too few
+fn rounded_digits<T: FixedPrecision>(magnitude: u128, decimals: u32) -> (u128, String) {
+    let scale = FixedDecimal::<T>::scale() as u128;
+    match T::PRECISION.checked_sub(decimals) {
+        Some(dropped) => {
+            let divisor = 10u128.pow(dropped);
+            let (mut kept, remainder) = (magnitude / divisor, magnitude % divisor);
+            if remainder * 2 > divisor || (remainder * 2 == divisor && kept % 2 == 1) {
+                kept += 1;
+            }
+            if decimals == 0 {
+                return (kept, String::new());
+            }
+            let unit = 10u128.pow(decimals);
+            let fraction = format!("{:0width$}", kept % unit, width = decimals as usize);
+            (kept / unit, fraction)
+        }
+        None => {
+            let digits = format!(
+                "{:0width$}",
+                magnitude % scale,
+                width = T::PRECISION as usize
+            );
+            (
+                magnitude / scale,
+                format!("{:0<width$}", digits, width = decimals as usize),
+            )
+        }
+    }
+}
+
+/// Currency view of a `FixedDecimal`, returned by `FixedDecimal::currency`.
+#[derive(Debug, Clone, Copy)]
+pub struct CurrencyFormat<'a, T: FixedPrecision> {
+    value: FixedDecimal<T>,
+    symbol: &'a str,
+    decimals: u32,
+    grouping: Option<char>,
+}
+
+impl<T: FixedPrecision> fmt::Display for CurrencyFormat<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (integer, fraction) = rounded_digits::<T>(self.value.0.unsigned_abs(), self.decimals);
+        let mut out = String::new();
+        if self.value.0 < 0 && (integer != 0 || fraction.bytes().any(|b| b != b'0')) {
+            out.push('-');
+        }
+        out.push_str(self.symbol);
+        push_grouped(&mut out, integer, self.grouping);
+        if self.decimals > 0 {
+            out.push('.');
+            out.push_str(&fraction);
+        }
+        f.write_str(&out)
     }
 }
 
 impl<T: FixedPrecision> fmt::Debug for FixedDecimal<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        fmt::Display::fmt(self, f)
     }
 }
 
@@ -363,7 +1175,7 @@ impl<T: FixedPrecision> DivAssign for FixedDecimal<T> {
 
 impl<T: FixedPrecision> PartialOrd<FixedDecimal<T>> for FixedDecimal<T> {
     fn partial_cmp(&self, other: &FixedDecimal<T>) -> Option<Ordering> {
-        Some(self.0.cmp(&other.0))
+        Some(self.cmp(other))
     }
 }
 
@@ -461,6 +1273,10 @@ macro_rules! impl_fixed_ops_with_primitive {
 
 impl_fixed_ops_with_primitive!(i128, i64, i32, usize, u64, u32);
 
+// The shift operators act on the raw value like the underlying `i128`
+// shifts: a shift amount of 128 or more (or a negative one) panics in debug
+// builds and is masked in release builds, and bits pushed out by a left shift
+// are lost without any signal. `checked_shl` and `checked_shr` report both.
 macro_rules! impl_fixed_shift_ops {
     ($($t:ty),*) => {
         $(
@@ -527,6 +1343,36 @@ macro_rules! impl_fixed_comparisons {
 
 impl_fixed_comparisons!(i128, i64, i32, isize, u128, u64, u32, usize);
 
+// Comparisons with `f64` go through `to_f64`, which keeps only 53 bits of
+// the raw value: beyond about 15 significant digits distinct fixed values
+// compare equal to the same float, and most decimal fractions (`0.1`) are
+// not exact in either type. Use `approx_eq_f64` with a tolerance instead of
+// `==` unless both sides are known to be exactly representable. NaN compares
+// unequal and unordered to everything.
+impl<T: FixedPrecision> PartialEq<f64> for FixedDecimal<T> {
+    fn eq(&self, other: &f64) -> bool {
+        self.to_f64() == *other
+    }
+}
+
+impl<T: FixedPrecision> PartialEq<FixedDecimal<T>> for f64 {
+    fn eq(&self, other: &FixedDecimal<T>) -> bool {
+        *self == other.to_f64()
+    }
+}
+
+impl<T: FixedPrecision> PartialOrd<f64> for FixedDecimal<T> {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        self.to_f64().partial_cmp(other)
+    }
+}
+
+impl<T: FixedPrecision> PartialOrd<FixedDecimal<T>> for f64 {
+    fn partial_cmp(&self, other: &FixedDecimal<T>) -> Option<Ordering> {
+        self.partial_cmp(&other.to_f64())
+    }
+}
+
 impl<T: FixedPrecision> Sum for FixedDecimal<T> {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(FixedDecimal::from_raw(0), |acc, x| acc + x)
@@ -557,8 +1403,122 @@ impl<'de, T: FixedPrecision> Deserialize<'de> for FixedDecimal<T> {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        FixedDecimal::from_str(&s).map_err(serde::de::Error::custom)
+        deserializer.deserialize_str(DecimalVisitor(std::marker::PhantomData))
+    }
+}
+
+struct DecimalVisitor<T: FixedPrecision>(std::marker::PhantomData<T>);
+
+impl<T: FixedPrecision> serde::de::Visitor<'_> for DecimalVisitor<T> {
+    type Value = FixedDecimal<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a decimal string of at most {} bytes", T::MAX_STR_LEN)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.len() > T::MAX_STR_LEN {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        FixedDecimal::from_str(v).map_err(E::custom)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T: FixedPrecision> arbitrary::Arbitrary<'a> for FixedDecimal<T> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::from_raw(i128::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        i128::size_hint(depth)
+    }
+}
+
+// SAFETY: `FixedDecimal` is `repr(transparent)` over an `i128`; the only
+// other field is a zero-sized `PhantomData`, so every bit pattern is valid,
+// all-zero is `zero()`, and there is no padding.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: FixedPrecision> bytemuck::Zeroable for FixedDecimal<T> {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: FixedPrecision + 'static> bytemuck::Pod for FixedDecimal<T> {}
+
+/// Described as a decimal string, matching what the serde impls produce, with
+/// a pattern allowing at most `T::PRECISION` fractional digits.
+#[cfg(feature = "schemars")]
+impl<T: FixedPrecision> schemars::JsonSchema for FixedDecimal<T> {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        format!("FixedDecimal{}", T::PRECISION).into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let pattern = if T::PRECISION == 0 {
+            "^-?[0-9]+$".to_string()
+        } else {
+            format!("^-?[0-9]+(\\.[0-9]{{1,{}}})?$", T::PRECISION)
+        };
+        let example = if T::PRECISION == 0 { "-12" } else { "-12.5" };
+        schemars::json_schema!({
+            "type": "string",
+            "pattern": pattern,
+            "examples": [example],
+        })
+    }
+}
+
+/// Encodes the raw value as 16 little-endian bytes, the same layout as
+/// `to_le_bytes`.
+#[cfg(feature = "borsh")]
+impl<T: FixedPrecision> borsh::BorshSerialize for FixedDecimal<T> {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        borsh::BorshSerialize::serialize(&self.0, writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl<T: FixedPrecision> borsh::BorshDeserialize for FixedDecimal<T> {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self::from_raw(i128::deserialize_reader(reader)?))
+    }
+}
+
+/// Conversions to and from `rust_decimal::Decimal`, which stores a 96-bit
+/// mantissa and a scale of at most 28, mapped directly onto the raw value.
+#[cfg(feature = "rust_decimal")]
+impl<T: FixedPrecision> FixedDecimal<T> {
+    /// The same value as a `Decimal` with scale `T::PRECISION`. Trailing
+    /// digits are truncated when the precision exceeds 28 or the raw value
+    /// needs more than 96 bits.
+    ///
+    /// # Panics
+    ///
+    /// If the integer part alone does not fit `Decimal`.
+    pub fn to_rust_decimal(&self) -> rust_decimal::Decimal {
+        let max = rust_decimal::Decimal::MAX.mantissa();
+        let (mut mantissa, mut scale) = (self.0, T::PRECISION);
+        while scale > rust_decimal::Decimal::MAX_SCALE || mantissa.unsigned_abs() > max as u128 {
+            assert!(scale > 0, "{} does not fit rust_decimal::Decimal", self);
+            mantissa /= 10;
+            scale -= 1;
+        }
+        rust_decimal::Decimal::from_i128_with_scale(mantissa, scale)
+    }
+
+    /// Exact conversion from a `Decimal`. Fails with a `DomainError` when
+    /// its scale exceeds `T::PRECISION`, trailing zeros included, and with
+    /// `Overflow` when the rescaled value does not fit.
+    pub fn from_rust_decimal(d: rust_decimal::Decimal) -> CrateResult<Self> {
+        let shift = T::PRECISION
+            .checked_sub(d.scale())
+            .ok_or(FixedFastError::DomainError(
+                "rust_decimal scale exceeds the precision",
+            ))?;
+        10i128
+            .checked_pow(shift)
+            .and_then(|factor| d.mantissa().checked_mul(factor))
+            .map(Self::from_raw)
+            .ok_or(FixedFastError::Overflow)
     }
 }
 
@@ -0,0 +1,228 @@
+use core::fmt;
+use std::{
+    cmp::Ordering,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    error::{FixedFastError, Result},
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+};
+
+/// Half-width counterpart of `FixedDecimal` backed by an `i64`, for large
+/// arrays of low-precision values (F6 or less leaves about 9.2e12 of integer
+/// range). Arithmetic widens to `FixedDecimal<T>`, where no intermediate can
+/// overflow, and narrows the result back, so both types round identically.
+///
+/// Operators panic when the result does not fit in an `i64`; the `checked_*`
+/// methods return `Overflow` instead.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct FixedDecimal64<T: FixedPrecision>(i64, std::marker::PhantomData<T>);
+
+impl<T: FixedPrecision> FixedDecimal64<T> {
+    pub const fn scale() -> i64 {
+        10i64.pow(T::PRECISION)
+    }
+
+    pub const fn zero() -> Self {
+        Self::from_raw(0)
+    }
+
+    pub const fn one() -> Self {
+        Self::from_raw(Self::scale())
+    }
+
+    pub const fn from_raw(x: i64) -> Self {
+        Self(x, std::marker::PhantomData)
+    }
+
+    pub const fn to_raw(&self) -> i64 {
+        self.0
+    }
+
+    pub fn from_i64(x: i64) -> Self {
+        Self::from_raw(x * Self::scale())
+    }
+
+    pub fn to_i64(&self) -> i64 {
+        self.0 / Self::scale()
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(x: &str) -> Result<Self> {
+        Self::try_from(FixedDecimal::<T>::from_str(x)?)
+    }
+
+    /// Lossless conversion to the `i128`-backed type.
+    pub fn widen(self) -> FixedDecimal<T> {
+        FixedDecimal::from_raw(self.0 as i128)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::from_raw(self.0.abs())
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self> {
+        Self::try_from(self.widen().checked_add(rhs.widen())?)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self> {
+        Self::try_from(self.widen().checked_sub(rhs.widen())?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self> {
+        Self::try_from(self.widen().checked_mul(rhs.widen())?)
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self> {
+        Self::try_from(self.widen().checked_div(rhs.widen())?)
+    }
+}
+
+impl<T: FixedPrecision> From<FixedDecimal64<T>> for FixedDecimal<T> {
+    fn from(x: FixedDecimal64<T>) -> Self {
+        x.widen()
+    }
+}
+
+impl<T: FixedPrecision> TryFrom<FixedDecimal<T>> for FixedDecimal64<T> {
+    type Error = FixedFastError;
+
+    fn try_from(x: FixedDecimal<T>) -> Result<Self> {
+        i64::try_from(x.to_raw())
+            .map(Self::from_raw)
+            .map_err(|_| FixedFastError::Overflow)
+    }
+}
+
+impl<T: FixedPrecision> fmt::Display for FixedDecimal64<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.widen(), f)
+    }
+}
+
+impl<T: FixedPrecision> fmt::Debug for FixedDecimal64<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<T: FixedPrecision> Add for FixedDecimal64<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("FixedDecimal64 addition overflowed")
+    }
+}
+
+impl<T: FixedPrecision> Sub for FixedDecimal64<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("FixedDecimal64 subtraction overflowed")
+    }
+}
+
+impl<T: FixedPrecision> Mul for FixedDecimal64<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("FixedDecimal64 multiplication overflowed")
+    }
+}
+
+impl<T: FixedPrecision> Div for FixedDecimal64<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("FixedDecimal64 division failed")
+    }
+}
+
+impl<T: FixedPrecision> Neg for FixedDecimal64<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::from_raw(-self.0)
+    }
+}
+
+impl<T: FixedPrecision> PartialOrd for FixedDecimal64<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: FixedPrecision> Ord for FixedDecimal64<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F6;
+
+    impl FixedPrecision for F6 {
+        const PRECISION: u32 = 6;
+    }
+
+    fn d(s: &str) -> FixedDecimal64<F6> {
+        FixedDecimal64::<F6>::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(std::mem::size_of::<FixedDecimal64<F6>>(), 8);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(d("1.5") + d("2.25"), d("3.75"));
+        assert_eq!(d("1.5") - d("2.25"), d("-0.75"));
+        assert_eq!(d("1.5") * d("-2.5"), d("-3.75"));
+        assert_eq!(d("1") / d("3"), d("0.333333"));
+        assert_eq!(-d("4"), FixedDecimal64::from_i64(-4));
+        assert_eq!(d("12.5").to_i64(), 12);
+        assert_eq!(d("-0.125").to_string(), "-0.125");
+        assert!(d("1") < d("1.000001"));
+        // results match the i128-backed type exactly
+        let (a, b) = (d("7.123456"), d("-3.654321"));
+        assert_eq!((a * b).widen(), a.widen() * b.widen());
+        assert_eq!((a / b).widen(), a.widen() / b.widen());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = FixedDecimal64::<F6>::from_raw(i64::MAX);
+        assert!(matches!(
+            max.checked_add(FixedDecimal64::from_raw(1)),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(matches!(
+            max.checked_mul(d("2")),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(matches!(
+            max.checked_div(d("0.5")),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(matches!(
+            d("1").checked_div(FixedDecimal64::zero()),
+            Err(FixedFastError::DivideByZero)
+        ));
+        // the product of two raw i64 values overflows i64 but not the result
+        assert_eq!(d("3000000").checked_mul(d("3")).unwrap(), d("9000000"));
+        assert!(FixedDecimal64::<F6>::from_str("10000000000000").is_err());
+        assert!(FixedDecimal64::<F6>::try_from(FixedDecimal::<F6>::from_i128(1 << 60)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedDecimal64 multiplication overflowed")]
+    fn test_operator_overflow_panics() {
+        let _ = FixedDecimal64::<F6>::from_raw(i64::MAX) * d("2");
+    }
+}
@@ -0,0 +1,550 @@
+use core::fmt;
+use std::{
+    cmp::Ordering,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    error::{FixedFastError, Result},
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    wide::I256,
+};
+
+/// High-precision counterpart of `FixedDecimal` backed by a 256-bit integer,
+/// for precisions such as F30 where the `i128` type has almost no integer
+/// range left. Products and quotients go through a 512-bit intermediate, so
+/// any result that fits in the backing integer is exact up to truncation.
+///
+/// `T::PRECISION` may be at most 76. Operators panic when the result does not
+/// fit; the `checked_*` methods return an error instead.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct FixedDecimalWide<T: FixedPrecision>(I256, std::marker::PhantomData<T>);
+
+impl<T: FixedPrecision> FixedDecimalWide<T> {
+    const SCALE: I256 = match I256::checked_pow10(T::PRECISION) {
+        Some(scale) => scale,
+        None => panic!("FixedDecimalWide supports at most 76 digits"),
+    };
+
+    const fn from_wide(x: I256) -> Self {
+        Self(x, std::marker::PhantomData)
+    }
+
+    pub const fn zero() -> Self {
+        Self::from_wide(I256::ZERO)
+    }
+
+    pub const fn one() -> Self {
+        Self::from_wide(Self::SCALE)
+    }
+
+    pub fn from_i128(x: i128) -> Self {
+        Self::from_wide(
+            I256::from_i128(x)
+                .checked_mul(Self::SCALE)
+                .expect("integer out of range for FixedDecimalWide"),
+        )
+    }
+
+    /// Lossless conversion from the `i128`-backed type at the same precision.
+    pub fn from_fixed(x: FixedDecimal<T>) -> Self {
+        Self::from_wide(I256::from_i128(x.to_raw()))
+    }
+
+    /// Narrows to the `i128`-backed type, failing with `Overflow` when the
+    /// raw value does not fit.
+    pub fn to_fixed(self) -> Result<FixedDecimal<T>> {
+        self.0
+            .to_i128()
+            .map(FixedDecimal::from_raw)
+            .ok_or(FixedFastError::Overflow)
+    }
+
+    /// Parses a decimal string, truncating fractional digits beyond
+    /// `T::PRECISION`, with the same errors as `FixedDecimal::from_str`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(x: &str) -> Result<Self> {
+        let is_negative = x.starts_with('-');
+        let x = x.strip_prefix(['-', '+']).unwrap_or(x);
+        let (integer_part, decimal_part) = x.split_once('.').unwrap_or((x, "0"));
+        // checked before truncating, which slices by bytes
+        if !decimal_part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(FixedFastError::DomainError("invalid decimal part"));
+        }
+        let decimal_part = &decimal_part[..decimal_part.len().min(T::PRECISION as usize)];
+
+        let integer = parse_digits(integer_part, "invalid integer part")?;
+        let decimal = parse_digits(decimal_part, "invalid decimal part")?;
+        let decimal_scale = I256::checked_pow10(T::PRECISION - decimal_part.len() as u32)
+            .ok_or(FixedFastError::Overflow)?;
+        let raw = integer
+            .checked_mul(Self::SCALE)
+            .and_then(|x| x.checked_add(decimal.checked_mul(decimal_scale)?))
+            .ok_or(FixedFastError::Overflow)?;
+        if is_negative {
+            raw.checked_neg()
+                .map(Self::from_wide)
+                .ok_or(FixedFastError::Overflow)
+        } else {
+            Ok(Self::from_wide(raw))
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.0 < I256::ZERO { -*self } else { *self }
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self> {
+        self.0
+            .checked_add(rhs.0)
+            .map(Self::from_wide)
+            .ok_or(FixedFastError::Overflow)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self> {
+        self.0
+            .checked_sub(rhs.0)
+            .map(Self::from_wide)
+            .ok_or(FixedFastError::Overflow)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self> {
+        self.0
+            .checked_mul_div(rhs.0, Self::SCALE)
+            .map(Self::from_wide)
+            .ok_or(FixedFastError::Overflow)
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self> {
+        if rhs.0 == I256::ZERO {
+            return Err(FixedFastError::DivideByZero);
+        }
+        self.0
+            .checked_mul_div(Self::SCALE, rhs.0)
+            .map(Self::from_wide)
+            .ok_or(FixedFastError::Overflow)
+    }
+
+    /// Square root truncated to the grid, using integer Newton iteration on
+    /// `raw * scale` from an initial guess above the root.
+    pub fn sqrt(self) -> Result<Self> {
+        if self.0 < I256::ZERO {
+            return Err(FixedFastError::DomainError(
+                "sqrt is undefined for negative numbers",
+            ));
+        }
+        if self.0 == I256::ZERO {
+            return Ok(Self::zero());
+        }
+        let two = I256::from_i128(2);
+        let mut y = I256::pow2((self.0.bits() + Self::SCALE.bits()).div_ceil(2));
+        loop {
+            let quotient = self
+                .0
+                .checked_mul_div(Self::SCALE, y)
+                .ok_or(FixedFastError::Overflow)?;
+            let next = y
+                .checked_add(quotient)
+                .and_then(|sum| sum.checked_div(two))
+                .ok_or(FixedFastError::Overflow)?;
+            if next >= y {
+                return Ok(Self::from_wide(y));
+            }
+            y = next;
+        }
+    }
+}
+
+// `exp` and `ln` evaluate their series at this many digits, whatever
+// `T::PRECISION` is, and truncate once when converting back. The series
+// values stay below 2, so they fit with room to spare.
+const WORKING_DIGITS: u32 = 70;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Working;
+
+impl FixedPrecision for Working {
+    const PRECISION: u32 = WORKING_DIGITS;
+}
+
+type WorkingDecimal = FixedDecimalWide<Working>;
+
+// ln 2 to `WORKING_DIGITS` digits, as `high * 10^35 + low` since the raw
+// value does not fit an `i128` literal
+const LN2: WorkingDecimal = WorkingDecimal::from_wide(
+    I256::mul(69314718055994530941723212145817656, 10i128.pow(35))
+        .checked_add(I256::from_i128(80755001343602552541206800094933936))
+        .unwrap(),
+);
+
+impl<T: FixedPrecision> FixedDecimalWide<T> {
+    // converts between `T` and `Working` raw values
+    const WORKING_SHIFT: I256 = match T::PRECISION <= WORKING_DIGITS {
+        true => I256::checked_pow10(WORKING_DIGITS - T::PRECISION).unwrap(),
+        false => panic!("exp and ln support at most 70 digits"),
+    };
+
+    /// `e^self`, truncated to the grid. Fails with `Overflow` when the
+    /// result does not fit; results below one ulp are zero. The series is
+    /// evaluated at 70 digits and keeps about 65 significant ones, so
+    /// results beyond `10^(65 - T::PRECISION)` can be off in their last
+    /// digits. `T::PRECISION` may be at most 70.
+    pub fn exp(self) -> Result<Self> {
+        let Some(x) = self.0.checked_mul(Self::WORKING_SHIFT) else {
+            return if self.0.is_negative() {
+                Ok(Self::zero())
+            } else {
+                Err(FixedFastError::Overflow)
+            };
+        };
+        let x = WorkingDecimal::from_wide(x);
+        // x = k ln2 + r with |r| < ln2, so e^x = 2^k e^r
+        let k = x
+            .checked_div(LN2)?
+            .0
+            .checked_div(WorkingDecimal::SCALE)
+            .and_then(I256::to_i128)
+            .ok_or(FixedFastError::Overflow)?;
+        if k <= -255 {
+            return Ok(Self::zero());
+        }
+        if k >= 255 {
+            return Err(FixedFastError::Overflow);
+        }
+        let r = x - LN2 * WorkingDecimal::from_i128(k);
+        let mut term = WorkingDecimal::one();
+        let mut sum = term;
+        let mut n = 1;
+        while term != WorkingDecimal::zero() {
+            term = term * r / WorkingDecimal::from_i128(n);
+            sum = sum + term;
+            n += 1;
+        }
+        let raw = if k >= 0 {
+            sum.0
+                .checked_mul_div(I256::pow2(k as u32), Self::WORKING_SHIFT)
+        } else {
+            // both divisions truncate a positive value, which is the same
+            // as dividing once by the product
+            sum.0
+                .checked_div(I256::pow2(-k as u32))
+                .and_then(|raw| raw.checked_div(Self::WORKING_SHIFT))
+        };
+        raw.map(Self::from_wide).ok_or(FixedFastError::Overflow)
+    }
+
+    /// Natural logarithm, truncated to the grid. `self` must be positive.
+    /// `T::PRECISION` may be at most 70.
+    pub fn ln(self) -> Result<Self> {
+        if self.0 <= I256::ZERO {
+            return Err(FixedFastError::DomainError(
+                "ln is undefined for non-positive numbers",
+            ));
+        }
+        // self = 2^k m with m in (1/2, 2), so ln(self) = k ln2 + ln(m)
+        let k = self.0.bits() as i32 - Self::SCALE.bits() as i32;
+        let m = if k >= 0 {
+            self.0
+                .checked_mul_div(Self::WORKING_SHIFT, I256::pow2(k as u32))
+        } else {
+            self.0
+                .checked_mul(Self::WORKING_SHIFT)
+                .and_then(|raw| raw.checked_mul(I256::pow2(-k as u32)))
+        }
+        .ok_or(FixedFastError::Overflow)?;
+        let ln = ln_series(WorkingDecimal::from_wide(m))
+            .checked_add(LN2.checked_mul(WorkingDecimal::from_i128(k as i128))?)?;
+        ln.0.checked_div(Self::WORKING_SHIFT)
+            .map(Self::from_wide)
+            .ok_or(FixedFastError::Overflow)
+    }
+}
+
+// ln(m) = 2 atanh((m - 1) / (m + 1)) for m in (1/2, 2], where the ratio is
+// at most 1/3 in magnitude and each term gains about a digit
+fn ln_series<T: FixedPrecision>(m: FixedDecimalWide<T>) -> FixedDecimalWide<T> {
+    let one = FixedDecimalWide::<T>::one();
+    let s = (m - one) / (m + one);
+    let s_squared = s * s;
+    let mut power = s;
+    let mut sum = s;
+    let mut n = 1;
+    loop {
+        power = power * s_squared;
+        let term = power / FixedDecimalWide::from_i128(2 * n + 1);
+        if term == FixedDecimalWide::zero() {
+            return sum + sum;
+        }
+        sum = sum + term;
+        n += 1;
+    }
+}
+
+fn parse_digits(digits: &str, message: &'static str) -> Result<I256> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(FixedFastError::DomainError(message));
+    }
+    let ten = I256::from_i128(10);
+    digits.bytes().try_fold(I256::ZERO, |acc, b| {
+        acc.checked_mul(ten)
+            .and_then(|acc| acc.checked_add(I256::from_i128((b - b'0') as i128)))
+            .ok_or(FixedFastError::Overflow)
+    })
+}
+
+/// Trailing fractional zeros are trimmed; the alternate flag (`{:#}`) keeps
+/// all `T::PRECISION` digits instead.
+impl<T: FixedPrecision> fmt::Display for FixedDecimalWide<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let raw = self.0.to_string();
+        let (sign, digits) = match raw.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", raw.as_str()),
+        };
+        let precision = T::PRECISION as usize;
+        let digits = format!("{:0>width$}", digits, width = precision + 1);
+        let (integer, decimal) = digits.split_at(digits.len() - precision);
+        let decimal = if f.alternate() {
+            decimal
+        } else {
+            decimal.trim_end_matches('0')
+        };
+
+        if decimal.is_empty() {
+            write!(f, "{}{}", sign, integer)
+        } else {
+            write!(f, "{}{}.{}", sign, integer, decimal)
+        }
+    }
+}
+
+impl<T: FixedPrecision> fmt::Debug for FixedDecimalWide<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl<T: FixedPrecision> Add for FixedDecimalWide<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("FixedDecimalWide addition overflowed")
+    }
+}
+
+impl<T: FixedPrecision> Sub for FixedDecimalWide<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("FixedDecimalWide subtraction overflowed")
+    }
+}
+
+impl<T: FixedPrecision> Mul for FixedDecimalWide<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("FixedDecimalWide multiplication overflowed")
+    }
+}
+
+impl<T: FixedPrecision> Div for FixedDecimalWide<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("FixedDecimalWide division failed")
+    }
+}
+
+impl<T: FixedPrecision> Neg for FixedDecimalWide<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::from_wide(
+            self.0
+                .checked_neg()
+                .expect("FixedDecimalWide negation overflowed"),
+        )
+    }
+}
+
+impl<T: FixedPrecision> PartialOrd for FixedDecimalWide<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: FixedPrecision> Ord for FixedDecimalWide<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F30;
+
+    impl FixedPrecision for F30 {
+        const PRECISION: u32 = 30;
+    }
+
+    fn d(s: &str) -> FixedDecimalWide<F30> {
+        FixedDecimalWide::<F30>::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_mul_beyond_i128() {
+        // raw values near 1e41 do not even fit the i128-backed type at F30
+        assert!(FixedDecimal::<F30>::from_str("123456789012.5").is_err());
+        let a = d("123456789012.5");
+        assert_eq!(a * a, d("15241578753276940725156.25"));
+        let b = d("98765432109.876543210987654321012345");
+        assert_eq!(
+            (a * b).to_string(),
+            "12193263113717421112621.7421112515050220728593125"
+        );
+        assert_eq!(
+            (a * b / d("7.25")).to_string(),
+            "1681829394995506360361.619601551931727182463353448275"
+        );
+        assert_eq!(-a * b, -(a * b));
+        assert!((a * a * a).checked_mul(a * a).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_and_conversions() {
+        assert_eq!(d("1") / d("3"), d("0.333333333333333333333333333333"));
+        assert_eq!(d("1.5") + d("-2.25"), d("-0.75"));
+        assert_eq!(d("1.5") - d("2.25"), d("-0.75"));
+        assert_eq!(d("-0.75").abs(), d("0.75"));
+        assert_eq!(d("-0.5").to_string(), "-0.5");
+        assert_eq!(FixedDecimalWide::<F30>::from_i128(-4).to_string(), "-4");
+        assert_eq!(format!("{:#}", d("1.5")), format!("1.5{}", "0".repeat(29)));
+        assert!(d("1") < d("1.000000000000000000000000000001"));
+
+        let narrow = FixedDecimal::<F30>::from_str("-12.000000000000000000000000000001").unwrap();
+        let wide = FixedDecimalWide::from_fixed(narrow);
+        assert_eq!(wide, d("-12.000000000000000000000000000001"));
+        assert_eq!(wide.to_fixed().unwrap(), narrow);
+        assert!(d("1000000000").to_fixed().is_err());
+
+        assert!(matches!(
+            d("1").checked_div(FixedDecimalWide::zero()),
+            Err(FixedFastError::DivideByZero)
+        ));
+        assert!(matches!(
+            FixedDecimalWide::<F30>::from_str("1.x"),
+            Err(FixedFastError::DomainError("invalid decimal part"))
+        ));
+        for malformed in ["1.2.3", "1.-5", "1.12345678901234567890123456789é"] {
+            assert!(matches!(
+                FixedDecimalWide::<F30>::from_str(malformed),
+                Err(FixedFastError::DomainError("invalid decimal part"))
+            ));
+        }
+        assert!(matches!(
+            FixedDecimalWide::<F30>::from_str("--5"),
+            Err(FixedFastError::DomainError("invalid integer part"))
+        ));
+        assert!(matches!(
+            FixedDecimalWide::<F30>::from_str(&"9".repeat(60)),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(
+            d("2").sqrt().unwrap(),
+            d("1.414213562373095048801688724209")
+        );
+        assert_eq!(
+            d("15241578753276940725156.25").sqrt().unwrap(),
+            d("123456789012.5")
+        );
+        assert_eq!(
+            FixedDecimalWide::<F30>::zero().sqrt().unwrap(),
+            FixedDecimalWide::zero()
+        );
+        assert!(d("-1").sqrt().is_err());
+    }
+
+    #[test]
+    fn test_exp() {
+        let cases = [
+            ("0", "1"),
+            ("1", "2.718281828459045235360287471352"),
+            ("-1", "0.367879441171442321595523770161"),
+            ("0.5", "1.648721270700128146848650787814"),
+            (
+                "2.302585092994045684017991454684",
+                "9.999999999999999999999999999996",
+            ),
+            (
+                "50",
+                "5184705528587072464087.453322933485384827469100583846",
+            ),
+            ("-50", "0.000000000000000000000192874984"),
+            ("-69", "0.000000000000000000000000000001"),
+        ];
+        for (x, expected) in cases {
+            assert_eq!(d(x).exp().unwrap(), d(expected), "exp({x})");
+        }
+        // about 65 significant digits survive, fewer than F30 shows here
+        let error = d("100").exp().unwrap()
+            - d("26881171418161354484126255515800135873611118.773741922415191608615280287034");
+        assert!(error.abs() < d("0.000000000000000000001"), "exp(100)");
+        assert_eq!(d("-1000").exp().unwrap(), FixedDecimalWide::zero());
+        assert!(matches!(d("110").exp(), Err(FixedFastError::Overflow)));
+        assert!(matches!(
+            d(&"9".repeat(40)).exp(),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_ln2_matches_series() {
+        assert_eq!(
+            LN2,
+            WorkingDecimal::from_str(
+                "0.6931471805599453094172321214581765680755001343602552541206800094933936"
+            )
+            .unwrap()
+        );
+        let tolerance = WorkingDecimal::from_str(&format!("0.{}1", "0".repeat(67))).unwrap();
+        assert!((ln_series(WorkingDecimal::from_i128(2)) - LN2).abs() < tolerance);
+    }
+
+    #[test]
+    fn test_ln() {
+        let cases = [
+            ("1", "0"),
+            ("2", "0.693147180559945309417232121458"),
+            ("10", "2.302585092994045684017991454684"),
+            ("0.5", "-0.693147180559945309417232121458"),
+            ("123456789012.5", "25.539157045251405086164156939057"),
+            (
+                "0.000000000000000000000000000001",
+                "-69.077552789821370520539743640530",
+            ),
+            (
+                "10000000000000000000000000000000000000000",
+                "92.103403719761827360719658187374",
+            ),
+        ];
+        for (x, expected) in cases {
+            assert_eq!(d(x).ln().unwrap(), d(expected), "ln({x})");
+        }
+        assert!(matches!(
+            FixedDecimalWide::<F30>::zero().ln(),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(d("-2").ln().is_err());
+        let x = d("3.75");
+        assert!(
+            (x.ln().unwrap().exp().unwrap() - x).abs() <= d("0.000000000000000000000000000005")
+        );
+    }
+}
@@ -5,8 +5,135 @@ use crate::{
 
 pub trait Function<T: FixedPrecision> {
     fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T>;
+
+    /// Fallible evaluation. Defaults to wrapping `evaluate`; implementations
+    /// that can detect bad inputs override it to return an error instead of
+    /// panicking.
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        Ok(self.evaluate(x))
+    }
+
+    /// Evaluates at `x` clamped into `[lo, hi]`, e.g. the domain of a
+    /// lookup table, so inputs past either bound take the value at that
+    /// bound instead of failing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo` is greater than `hi`.
+    fn evaluate_clamped(
+        &self,
+        x: FixedDecimal<T>,
+        lo: FixedDecimal<T>,
+        hi: FixedDecimal<T>,
+    ) -> FixedDecimal<T> {
+        self.evaluate(x.clamp(lo, hi))
+    }
+
+    /// Evaluates at `start`, `start + step`, ... up to and including every
+    /// grid point not past `end`, yielding `(x, y)` pairs. `end` itself is
+    /// only included when it lies on the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive.
+    fn sample_range(
+        &self,
+        start: FixedDecimal<T>,
+        end: FixedDecimal<T>,
+        step: FixedDecimal<T>,
+    ) -> impl Iterator<Item = (FixedDecimal<T>, FixedDecimal<T>)>
+    where
+        Self: Sized,
+    {
+        assert!(step > 0, "sample_range step must be positive");
+        std::iter::successors(Some(start), move |x| x.checked_add(step).ok())
+            .take_while(move |x| *x <= end)
+            .map(move |x| (x, self.evaluate(x)))
+    }
+}
+
+/// A function defined only at integer arguments, such as a factorial or a
+/// probability mass function, evaluated without going through a
+/// `FixedDecimal` input.
+pub trait IntFunction<T: FixedPrecision> {
+    fn evaluate_int(&self, n: i128) -> FixedDecimal<T>;
+
+    /// Fallible evaluation. Defaults to wrapping `evaluate_int`, like
+    /// `Function::evaluate_checked`.
+    fn try_evaluate_int(&self, n: i128) -> Result<FixedDecimal<T>> {
+        Ok(self.evaluate_int(n))
+    }
 }
 
 pub trait TryFunction<T: FixedPrecision> {
     fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>>;
 }
+
+// Every `Function` is usable where a `TryFunction` is expected. Types that
+// implement `Function` put their fallible logic in `Function::evaluate_checked`
+// rather than implementing `TryFunction` directly.
+impl<T: FixedPrecision, F: Function<T>> TryFunction<T> for F {
+    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        Function::evaluate_checked(self, x)
+    }
+}
+
+/// `output_scale * f(input_scale * x + input_offset) + output_offset`
+/// around an existing function, e.g. a normal CDF with mean `mu` and
+/// standard deviation `sigma` from the standard one with an input scale of
+/// `1 / sigma` and an input offset of `-mu / sigma`. Starts out as the
+/// identity transform.
+#[derive(Debug, Clone, Copy)]
+pub struct AffineWrap<T: FixedPrecision, F> {
+    f: F,
+    input_scale: FixedDecimal<T>,
+    input_offset: FixedDecimal<T>,
+    output_scale: FixedDecimal<T>,
+    output_offset: FixedDecimal<T>,
+}
+
+impl<T: FixedPrecision, F> AffineWrap<T, F> {
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            input_scale: FixedDecimal::one(),
+            input_offset: FixedDecimal::zero(),
+            output_scale: FixedDecimal::one(),
+            output_offset: FixedDecimal::zero(),
+        }
+    }
+
+    pub fn input(mut self, scale: FixedDecimal<T>, offset: FixedDecimal<T>) -> Self {
+        self.input_scale = scale;
+        self.input_offset = offset;
+        self
+    }
+
+    pub fn output(mut self, scale: FixedDecimal<T>, offset: FixedDecimal<T>) -> Self {
+        self.output_scale = scale;
+        self.output_offset = offset;
+        self
+    }
+
+    pub fn inner(&self) -> &F {
+        &self.f
+    }
+}
+
+impl<T: FixedPrecision, F: Function<T>> Function<T> for AffineWrap<T, F> {
+    fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        let y = self.f.evaluate(self.input_scale * x + self.input_offset);
+        self.output_scale * y + self.output_offset
+    }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        let x = self
+            .input_scale
+            .checked_mul(x)?
+            .checked_add(self.input_offset)?;
+        let y = Function::evaluate_checked(&self.f, x)?;
+        self.output_scale
+            .checked_mul(y)?
+            .checked_add(self.output_offset)
+    }
+}
@@ -12,3 +12,44 @@ pub fn linear_interpolation<T: FixedPrecision>(
     let t = x.sub(x1).div(dx);
     y1.add(t.mul(dy))
 }
+
+/// `linear_interpolation` together with the slope `dy / dx` of the segment
+/// from `(x1, y1)` to `(x2, y2)`.
+pub fn linear_interpolation_with_slope<T: FixedPrecision>(
+    x: FixedDecimal<T>,
+    x1: FixedDecimal<T>,
+    x2: FixedDecimal<T>,
+    y1: FixedDecimal<T>,
+    y2: FixedDecimal<T>,
+) -> (FixedDecimal<T>, FixedDecimal<T>) {
+    let slope = y2.sub(y1).div(x2.sub(x1));
+    (linear_interpolation(x, x1, x2, y1, y2), slope)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    fn d(x: &str) -> FixedDecimal<F9> {
+        FixedDecimal::from_str(x).unwrap()
+    }
+
+    #[test]
+    fn test_linear_interpolation_with_slope() {
+        let (x1, x2, y1, y2) = (d("1"), d("3"), d("2"), d("-3"));
+        for x in ["1", "1.5", "2.25", "3"] {
+            let (value, slope) = linear_interpolation_with_slope(d(x), x1, x2, y1, y2);
+            assert_eq!(value, linear_interpolation(d(x), x1, x2, y1, y2));
+            assert_eq!(slope, d("-2.5"));
+            // the slope carries `y1` to the interpolated value
+            assert_eq!(y1 + slope * (d(x) - x1), value);
+        }
+    }
+}
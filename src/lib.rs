@@ -1,31 +1,91 @@
+mod binomial_distribution;
+mod black_scholes;
 mod cdf;
+mod combinatorics;
+mod erf;
 mod error;
 mod exp;
+mod finance;
 mod fixed_decimal;
+mod fixed_decimal64;
+mod fixed_decimal_wide;
 mod function;
 mod interpolation;
 mod ln;
+mod lognormal;
 mod lookup_table;
 mod pdf;
+mod poisson;
+mod polynomial;
+mod precision;
+#[cfg(feature = "rand")]
+mod sampling;
+mod softmax;
+mod solver;
 mod sqrt;
+mod stats;
+#[cfg(feature = "proptest")]
+mod strategies;
+mod wide;
 
-pub use cdf::{CDFCustomAprox, CDFLinearInterpLookupTable, CDFV1};
+pub use binomial_distribution::{binomial_cdf, binomial_pmf};
+pub use black_scholes::{BlackScholes, OptionKind};
+pub use cdf::{
+    CDFCustomAprox, CDFErf, CDFLinearInterpLookupTable, CDFV1, bivariate_normal_cdf, probit,
+};
+pub use combinatorics::{BinomialCoefficient, Factorial, arrangements, binomial, factorial};
+pub use erf::{erf, erfc};
 pub use error::{FixedFastError, FixedPointError, FixedPointResult};
-pub use exp::{ExpLinearInterpLookupTable, ExpRangeReduceTaylor, ExpV1};
-pub use fixed_decimal::{FixedDecimal, FixedPrecision};
+pub use exp::{
+    ExpLinearInterpLookupTable, ExpRangeReduceTaylor, ExpV1, ExpV2, recommended_taylor_order,
+};
+pub use finance::{
+    annuity_pv, compound, continuous_discount, discount_factor, log_return, pct_change,
+};
+pub use fixed_decimal::{
+    ConstPrecision, CurrencyFormat, FixedDecimal, FixedDecimalC, FixedPrecision,
+};
+pub use fixed_decimal_wide::FixedDecimalWide;
+pub use fixed_decimal64::FixedDecimal64;
+#[cfg(not(feature = "safe"))]
 pub use function::Function;
 pub use function::TryFunction; // fallible trait
 #[cfg(feature = "safe")]
 pub use function::TryFunction as Function; // alias when safe feature is enabled
-pub use ln::{LnArcTanhExpansion, LnLinearInterpLookupTable, LnV1};
-pub use pdf::{PDFLinearInterpLookupTable, PDFV1};
-pub use sqrt::{SqrtLinearInterpLookupTable, SqrtNewtonRaphson, SqrtV1};
+pub use function::{AffineWrap, IntFunction};
+pub use ln::{LnArcTanhExpansion, LnHybrid, LnLinearInterpLookupTable, LnV1};
+pub use lognormal::{lognormal_cdf, lognormal_pdf};
+pub use lookup_table::{
+    Extrapolation, Hybrid, Interpolation, Local, LookupTable, LookupTableBuilder,
+};
+pub use pdf::{PDF, PDFLinearInterpLookupTable, PDFV1};
+pub use poisson::{Poisson, poisson_pmf};
+pub use polynomial::Polynomial;
+pub use precision::PrecisionGuard;
+#[cfg(feature = "rand")]
+pub use sampling::{sample_open_unit, sample_standard_normal};
+pub use softmax::{log_sum_exp, softmax};
+pub use solver::{bisection, newton_raphson};
+pub use sqrt::{
+    SqrtHybrid, SqrtLinearInterpLookupTable, SqrtNewtonRaphson, SqrtV1, SqrtV2,
+    recommended_sqrt_depth,
+};
+pub use stats::{
+    RunningStats, cumulative_product, cumulative_sum, geometric_mean, harmonic_mean, mean,
+    quantile, sample_std_dev, sample_variance, std_dev, try_cumulative_product, try_cumulative_sum,
+    variance, weighted_mean,
+};
+#[cfg(feature = "proptest")]
+pub use strategies::any_fixed;
 
 // Re-export fallible helpers for convenience
+pub use exp::range_reduce_taylor_exp_try as exp_try;
 pub use ln::range_reduce_arctanh_ln_try as ln_try;
+pub use sqrt::sqrt_newton_raphson_converge as sqrt_converge;
 pub use sqrt::sqrt_newton_raphson_try as sqrt_try;
 #[cfg(test)]
 mod tests {
+    use crate::error::FixedFastError;
     use crate::fixed_decimal::{FixedDecimal, FixedPrecision};
 
     const ONE_SCALED_INTEGER: i128 = 1000000000;
@@ -120,6 +180,34 @@ mod tests {
         assert_eq!(d.to_f64(), 1797888.0);
     }
 
+    #[test]
+    fn const_generic_precision() {
+        use crate::fixed_decimal::FixedDecimalC;
+
+        let a = FixedDecimalC::<9>::from_i128(1325235);
+        let b = FixedDecimalC::<9>::from_i128(3123123);
+        assert_eq!((a * b).to_f64(), 4138871908905.0);
+        assert_eq!((a / b).to_f64(), 0.424330069);
+        assert_eq!((a + b).to_f64(), 4448358.0);
+        assert_eq!((a - b).to_f64(), -1797888.0);
+        let d: FixedDecimalC<9> = 3123123 / a;
+        assert_eq!(d.to_f64(), 2.356655989);
+        assert_eq!(FixedDecimalC::<9>::one().to_raw(), ONE_SCALED_INTEGER);
+
+        // same raw values and formatting as the marker-struct form
+        for s in ["1.3453453453453453", "-4.25", "0.000000000000000001"] {
+            let c = FixedDecimalC::<18>::from_str(s).unwrap();
+            let f = FixedDecimal::<F18>::from_str(s).unwrap();
+            assert_eq!(c.to_raw(), f.to_raw());
+            assert_eq!(c.to_string(), f.to_string());
+            assert_eq!((c * c).to_raw(), (f * f).to_raw());
+            assert_eq!((c / 3i128).to_raw(), (f / 3i128).to_raw());
+        }
+        let root = crate::sqrt_try::<_, 12>(FixedDecimalC::<18>::from_i128(2)).unwrap();
+        let expected = crate::sqrt_try::<F18, 12>(FixedDecimal::from_i128(2)).unwrap();
+        assert_eq!(root.to_raw(), expected.to_raw());
+    }
+
     #[test]
     fn mul_assign() {
         let mut a = FixedDecimal::<F9>::from_i128(1);
@@ -171,6 +259,23 @@ mod tests {
                 .checked_sub(FixedDecimal::<F9>::one())
                 .is_err()
         );
+
+        // only results that do not fit overflow, not the raw intermediates
+        let a = FixedDecimal::<F18>::from_i128(1000);
+        let b = FixedDecimal::<F18>::from_str("1.5").unwrap();
+        assert_eq!(a.checked_mul(b).unwrap(), FixedDecimal::from_i128(1500));
+        assert_eq!(
+            a.checked_div(b).unwrap(),
+            FixedDecimal::from_str("666.666666666666666666").unwrap()
+        );
+        assert!(matches!(
+            big.checked_mul(FixedDecimal::from_str("1.5").unwrap()),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(matches!(
+            big.checked_div(FixedDecimal::from_str("0.5").unwrap()),
+            Err(FixedFastError::Overflow)
+        ));
     }
 
     #[test]
@@ -207,6 +312,8 @@ mod tests {
         assert_eq!(a.to_f64(), -1797888.0);
         let mut a = FixedDecimal::<F9>::from_i128(1325235);
         let b = 3123123;
+        a -= b;
+        assert_eq!(a.to_f64(), -1797888.0);
     }
 
     #[test]
@@ -225,6 +332,191 @@ mod tests {
         assert_eq!(c.to_f64(), 0.424330069);
     }
 
+    #[test]
+    fn lerp() {
+        let a = FixedDecimal::<F9>::from_i128(2);
+        let b = FixedDecimal::<F9>::from_i128(-6);
+        assert_eq!(a.lerp(b, FixedDecimal::zero()), a);
+        assert_eq!(a.lerp(b, FixedDecimal::one()), b);
+        assert_eq!(
+            a.lerp(b, FixedDecimal::from_str("0.5").unwrap()),
+            FixedDecimal::<F9>::from_i128(-2)
+        );
+        assert_eq!(
+            a.lerp(b, FixedDecimal::from_str("1.5").unwrap()),
+            FixedDecimal::<F9>::from_i128(-10)
+        );
+    }
+
+    #[test]
+    fn ema_step() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let alpha = d("0.2");
+        let mut state = d("100");
+        let mut manual = state;
+        for x in ["101", "99.5", "102.25", "98", "100.125"] {
+            state = state.ema_step(d(x), alpha);
+            manual = manual + alpha * (d(x) - manual);
+            assert_eq!(state, manual);
+        }
+        assert_eq!(state, d("100.02372"));
+        assert_eq!(d("5").ema_step(d("9"), FixedDecimal::zero()), d("5"));
+        assert_eq!(d("5").ema_step(d("9"), FixedDecimal::one()), d("9"));
+
+        // alpha * (x - state) overflows the plain multiply at F18
+        let big = FixedDecimal::<F18>::from_i128(10_000_000_000);
+        let half = FixedDecimal::<F18>::from_str("0.5").unwrap();
+        assert_eq!((-big).ema_step(big, half), FixedDecimal::<F18>::zero());
+        // x - state does not fit in i128 at all
+        let lowest = FixedDecimal::<F18>::from_raw(i128::MIN + 1);
+        let highest = FixedDecimal::<F18>::from_raw(i128::MAX);
+        assert_eq!(lowest.ema_step(highest, half), FixedDecimal::<F18>::zero());
+        assert_eq!(lowest.ema_step(highest, FixedDecimal::one()), highest);
+    }
+
+    #[test]
+    fn hypot() {
+        let a = FixedDecimal::<F18>::from_i128(3);
+        let b = FixedDecimal::<F18>::from_i128(-4);
+        assert_eq!(a.hypot(b), FixedDecimal::from_i128(5));
+        assert_eq!(b.hypot(a), FixedDecimal::from_i128(5));
+        assert_eq!(
+            FixedDecimal::<F18>::zero().hypot(FixedDecimal::zero()),
+            FixedDecimal::zero()
+        );
+        assert_eq!(a.hypot(FixedDecimal::zero()), a);
+
+        // the naive squares overflow i128 well before the result does
+        let a = FixedDecimal::<F18>::from_i128(30_000_000_000_000_000_000);
+        let b = FixedDecimal::<F18>::from_i128(40_000_000_000_000_000_000);
+        assert!(a.checked_mul(a).is_err());
+        assert_eq!(
+            a.hypot(b),
+            FixedDecimal::from_i128(50_000_000_000_000_000_000)
+        );
+        let c = FixedDecimal::<F18>::from_i128(1_000_000);
+        // the root's truncation error is scaled up by the larger input
+        let exact = FixedDecimal::<F18>::from_str("1414213.562373095048801688").unwrap();
+        assert!((c.hypot(c) - exact).abs() < FixedDecimal::from_str("0.000000000001").unwrap());
+    }
+
+    #[test]
+    fn hypot_high_precision() {
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        struct F30;
+
+        impl FixedPrecision for F30 {
+            const PRECISION: u32 = 30;
+        }
+
+        let d = |s| FixedDecimal::<F30>::from_str(s).unwrap();
+        // a ratio of one squares raw values of 10^30
+        assert_eq!(d("1").hypot(d("1")), d("1.414213562373095048801688724209"));
+        assert_eq!(d("3").hypot(d("-4")), d("5"));
+    }
+
+    #[test]
+    fn pow_i128() {
+        let a = FixedDecimal::<F9>::from_str("1.5").unwrap();
+        assert_eq!(a.pow_i128(3), FixedDecimal::from_str("3.375").unwrap());
+        assert_eq!(a.pow_i128(0), FixedDecimal::one());
+        assert_eq!(
+            a.pow_i128(-2),
+            FixedDecimal::from_str("0.444444444").unwrap()
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::from_i128(2).pow_i128(-3),
+            FixedDecimal::from_str("0.125").unwrap()
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::one().pow_i128(i128::MIN),
+            FixedDecimal::one()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "zero to a negative power")]
+    fn pow_i128_zero_negative_power() {
+        FixedDecimal::<F9>::zero().pow_i128(-1);
+    }
+
+    #[test]
+    fn saturating_pow() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let max = FixedDecimal::<F18>::from_raw(i128::MAX);
+        let min = FixedDecimal::<F18>::from_raw(i128::MIN);
+        assert_eq!(d("1.5").saturating_pow(4), d("5.0625"));
+        assert_eq!(d("-2").saturating_pow(5), d("-32"));
+        assert_eq!(d("0.5").saturating_pow(10), d("0.0009765625"));
+        assert_eq!(d("7").saturating_pow(0), FixedDecimal::one());
+        assert_eq!(d("1.05").saturating_pow(1), d("1.05"));
+        assert_eq!(d("10").saturating_pow(20), d("100000000000000000000"));
+        assert_eq!(d("10").saturating_pow(21), max);
+        assert_eq!(d("1.1").saturating_pow(1000), max);
+        assert_eq!(d("-10").saturating_pow(21), min);
+        assert_eq!(d("-10").saturating_pow(22), max);
+        assert_eq!(max.saturating_pow(u32::MAX), max);
+        let rate = FixedDecimal::<F9>::from_str("1.07").unwrap();
+        assert_eq!(rate.saturating_pow(3), rate.pow_i128(3));
+    }
+
+    #[test]
+    fn checked_pow() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        assert_eq!(d("1.5").checked_pow(3).unwrap(), d("3.375"));
+        assert_eq!(d("-2").checked_pow(5).unwrap(), d("-32"));
+        assert_eq!(d("7").checked_pow(0).unwrap(), FixedDecimal::one());
+        assert_eq!(d("2").checked_pow(-3).unwrap(), d("0.125"));
+        assert_eq!(d("0.5").checked_pow(-10).unwrap(), d("1024"));
+        assert_eq!(d("1.5").checked_pow(-2).unwrap(), d("0.444444444444444444"));
+        assert!(matches!(
+            d("10").checked_pow(21),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(matches!(
+            d("1.1").checked_pow(1000),
+            Err(FixedFastError::Overflow)
+        ));
+        // 0.5^200 truncates to zero, but its reciprocal overflows
+        assert!(matches!(
+            d("0.5").checked_pow(-200),
+            Err(FixedFastError::Overflow)
+        ));
+        // 2^100 overflows, but its reciprocal is below one ulp
+        assert_eq!(d("2").checked_pow(-100).unwrap(), FixedDecimal::zero());
+        assert!(matches!(
+            FixedDecimal::<F18>::zero().checked_pow(-1),
+            Err(FixedFastError::DivideByZero)
+        ));
+        assert_eq!(d("1").checked_pow(i128::MIN).unwrap(), FixedDecimal::one());
+        assert_eq!(d("-1").checked_pow(i128::MIN).unwrap(), FixedDecimal::one());
+        assert_eq!(d("2").checked_pow(i128::MIN).unwrap(), FixedDecimal::zero());
+        assert_eq!(d("-1").checked_pow(i128::MAX).unwrap(), d("-1"));
+        let rate = d("1.07");
+        assert_eq!(rate.checked_pow(7).unwrap(), rate.saturating_pow(7));
+    }
+
+    #[test]
+    fn next_up_down() {
+        let zero = FixedDecimal::<F9>::zero();
+        assert_eq!(zero.next_up(), FixedDecimal::ulp());
+        assert_eq!(zero.next_down(), -FixedDecimal::<F9>::ulp());
+        let x = FixedDecimal::<F9>::from_str("2.5").unwrap();
+        assert_eq!(x.next_up(), FixedDecimal::from_str("2.500000001").unwrap());
+        assert_eq!(
+            x.next_down(),
+            FixedDecimal::from_str("2.499999999").unwrap()
+        );
+        assert_eq!(x.next_up().next_down(), x);
+        assert_eq!(x.next_up().ulp_distance(x), 1);
+        let max = FixedDecimal::<F18>::from_raw(i128::MAX);
+        let min = FixedDecimal::<F18>::from_raw(i128::MIN);
+        assert_eq!(max.next_down().next_up(), max);
+        assert_eq!(max.next_up(), max);
+        assert_eq!(min.next_down(), min);
+        assert_eq!(min.next_up().to_raw(), i128::MIN + 1);
+    }
+
     #[test]
     fn squared() {
         let a = FixedDecimal::<F9>::from_i128(2);
@@ -235,12 +527,50 @@ mod tests {
         assert_eq!(b.to_f64(), 1755625.0);
     }
 
+    #[test]
+    fn checked_powers() {
+        let x = FixedDecimal::<F9>::from_str("-1.5").unwrap();
+        assert_eq!(x.checked_squared().unwrap(), x.squared());
+        assert_eq!(x.checked_cubed().unwrap(), x.cubed());
+        assert_eq!(x.checked_tesseracted().unwrap(), x.tesseracted());
+
+        // largest integers whose powers still fit at F18
+        let boundaries = [13_043_817_825, 5_541_191, 114_209];
+        type Power = fn(&FixedDecimal<F18>) -> Result<FixedDecimal<F18>, FixedFastError>;
+        let powers: [Power; 3] = [
+            FixedDecimal::checked_squared,
+            FixedDecimal::checked_cubed,
+            FixedDecimal::checked_tesseracted,
+        ];
+        for (n, (boundary, power)) in boundaries.into_iter().zip(powers).enumerate() {
+            let fits = FixedDecimal::<F18>::from_i128(boundary);
+            let expected = (0..=n).fold(boundary, |acc, _| acc * boundary);
+            assert_eq!(power(&fits).unwrap(), FixedDecimal::from_i128(expected));
+            assert_eq!(
+                power(&-fits).unwrap().abs(),
+                FixedDecimal::from_i128(expected)
+            );
+            let over = fits + FixedDecimal::one();
+            assert!(matches!(power(&over), Err(FixedFastError::Overflow)));
+        }
+    }
+
     #[test]
     fn scale() {
         let a = FixedDecimal::<F9>::scale();
         assert_eq!(a, ONE_SCALED_INTEGER);
     }
 
+    #[test]
+    fn precision() {
+        assert_eq!(FixedDecimal::<F9>::precision(), 9);
+        assert_eq!(FixedDecimal::<F18>::DECIMAL_DIGITS, 18);
+        assert_eq!(
+            10i128.pow(FixedDecimal::<F9>::DECIMAL_DIGITS),
+            FixedDecimal::<F9>::scale()
+        );
+    }
+
     #[test]
     fn to_integer() {
         let a = FixedDecimal::<F9>::from_i128(1);
@@ -249,7 +579,7 @@ mod tests {
 
     #[test]
     fn sum_vec() {
-        let vec = vec![
+        let vec = [
             FixedDecimal::<F9>::from_i128(1),
             FixedDecimal::<F9>::from_i128(2),
             FixedDecimal::<F9>::from_i128(3),
@@ -258,6 +588,54 @@ mod tests {
     }
 
     #[test]
+    fn ulp() {
+        let a = FixedDecimal::<F9>::from_str("1.5").unwrap();
+        assert_eq!(FixedDecimal::<F9>::ulp(), FixedDecimal::min_positive());
+        assert_eq!(FixedDecimal::<F9>::ulp().to_string(), "0.000000001");
+        assert_eq!(a.ulp_distance(a), 0);
+        assert_eq!(a.ulp_distance(a + FixedDecimal::ulp()), 1);
+        assert_eq!((a + FixedDecimal::ulp()).ulp_distance(a), 1);
+        assert_eq!(a.ulp_distance(-a), 3_000_000_000);
+        let min = FixedDecimal::<F9>::from_raw(i128::MIN);
+        let max = FixedDecimal::<F9>::from_raw(i128::MAX);
+        assert_eq!(min.ulp_distance(max), i128::MAX);
+    }
+
+    #[test]
+    fn raw_bits() {
+        let zero = FixedDecimal::<F9>::zero();
+        assert_eq!(zero.raw_bits(), 0);
+        assert_eq!(zero.leading_zeros(), 128);
+        let small = FixedDecimal::<F9>::from_raw(5);
+        assert_eq!(small.raw_bits(), 3);
+        assert_eq!(small.leading_zeros(), 125);
+        assert_eq!((-small).raw_bits(), 3);
+        let near_max = FixedDecimal::<F9>::from_raw(i128::MAX);
+        assert_eq!(near_max.raw_bits(), 127);
+        assert_eq!(near_max.leading_zeros(), 1);
+        assert_eq!(FixedDecimal::<F9>::from_raw(i128::MIN).raw_bits(), 128);
+    }
+
+    #[test]
+    fn checked_sum() {
+        let values = [1, 2, 3, -4].map(FixedDecimal::<F9>::from_i128);
+        assert_eq!(
+            FixedDecimal::checked_sum(values.into_iter()).unwrap(),
+            values.iter().sum::<FixedDecimal<F9>>()
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::checked_sum(std::iter::empty()).unwrap(),
+            0
+        );
+        let big = [FixedDecimal::<F9>::from_raw(i128::MAX / 2); 3];
+        assert!(matches!(
+            FixedDecimal::checked_sum(big.into_iter()),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    #[allow(clippy::nonminimal_bool)]
     fn ordering() {
         let a = FixedDecimal::<F9>::from_i128(1);
         let b = FixedDecimal::<F9>::from_i128(2);
@@ -295,6 +673,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn checked_shifts() {
+        let a = FixedDecimal::<F18>::from_i128(3);
+        assert_eq!(a.checked_shl(2).unwrap(), a << 2);
+        assert_eq!(
+            (-a).checked_shl(2).unwrap(),
+            FixedDecimal::<F18>::from_i128(-12)
+        );
+        assert_eq!(a.checked_shr(1).unwrap(), a >> 1);
+        // 3 * 10^18 needs 62 bits, so a shift of 66 pushes a bit into the sign
+        assert_eq!(
+            a.checked_shl(65).unwrap(),
+            FixedDecimal::<F18>::from_raw(3_000_000_000_000_000_000 << 65)
+        );
+        assert!(matches!(a.checked_shl(66), Err(FixedFastError::Overflow)));
+        assert!(matches!(
+            a.checked_shl(128),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(matches!(
+            a.checked_shr(128),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert_eq!((-a).checked_shr(127).unwrap(), FixedDecimal::from_raw(-1));
+    }
+
+    #[test]
+    fn checked_two_pow_k() {
+        // one() at F18 is just under 2^60, leaving 67 bits of headroom
+        assert_eq!(
+            FixedDecimal::<F18>::checked_two_pow_k(3).unwrap(),
+            FixedDecimal::<F18>::from_i128(8)
+        );
+        assert_eq!(
+            FixedDecimal::<F18>::checked_two_pow_k(67).unwrap(),
+            FixedDecimal::<F18>::two_pow_k(67)
+        );
+        assert!(matches!(
+            FixedDecimal::<F18>::checked_two_pow_k(68),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(FixedDecimal::<F18>::checked_two_pow_k(1000).is_err());
+        // 10^18 >> 59 is the last non-zero result
+        assert_eq!(
+            FixedDecimal::<F18>::checked_two_pow_k(-59).unwrap(),
+            FixedDecimal::<F18>::from_raw(1)
+        );
+        assert!(matches!(
+            FixedDecimal::<F18>::checked_two_pow_k(-60),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(FixedDecimal::<F18>::checked_two_pow_k(-1000).is_err());
+    }
+
+    #[test]
+    fn log2_e() {
+        let a = FixedDecimal::<F18>::log2_e();
+        assert_eq!(
+            a,
+            FixedDecimal::<F18>::from_str("1.442695040888963407").unwrap()
+        );
+    }
+
     #[test]
     fn e() {
         let a = FixedDecimal::<F18>::e();
@@ -313,6 +754,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normal_constants() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        assert_eq!(
+            FixedDecimal::<F18>::inv_sqrt_2pi(),
+            d("0.398942280401432677")
+        );
+        assert_eq!(FixedDecimal::<F18>::sqrt_2pi(), d("2.506628274631000502"));
+        assert_eq!(
+            FixedDecimal::<F18>::euler_gamma(),
+            d("0.577215664901532860")
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::inv_sqrt_2pi(),
+            FixedDecimal::<F9>::from_str("0.398942280").unwrap()
+        );
+
+        // the runtime values the constants replace agree to the last digit,
+        // which the constants truncate rather than round
+        let sqrt_2pi = crate::sqrt_try::<F18, 20>(FixedDecimal::pi() * 2).unwrap();
+        assert!(sqrt_2pi.ulp_distance(FixedDecimal::sqrt_2pi()) <= 1);
+        let inv_sqrt_2pi: FixedDecimal<F18> = 1 / sqrt_2pi;
+        assert!(inv_sqrt_2pi.ulp_distance(FixedDecimal::inv_sqrt_2pi()) <= 1);
+        // both truncations and the product's own leave it 3 ulps short
+        assert_eq!(
+            FixedDecimal::<F18>::inv_sqrt_2pi() * FixedDecimal::sqrt_2pi(),
+            FixedDecimal::from_raw(999_999_999_999_999_997)
+        );
+    }
+
     #[test]
     fn negatives() {
         let a = FixedDecimal::<F18>::from_i128(-10);
@@ -321,6 +792,283 @@ mod tests {
         assert_eq!(b, FixedDecimal::<F18>::from_str("-5").unwrap());
         let c = FixedDecimal::<F18>::from_str("-12.231231").unwrap();
         assert_eq!(c, FixedDecimal::<F18>::from_str("-12.231231").unwrap());
+        // the sign must survive an integer part of zero
+        let d = FixedDecimal::<F18>::from_str("-0.5").unwrap();
+        assert_eq!(d.to_string(), "-0.5");
+        let min = FixedDecimal::<F18>::from_raw(i128::MIN);
+        assert_eq!(
+            FixedDecimal::<F18>::from_str(&min.to_string()).unwrap(),
+            min
+        );
+    }
+
+    #[test]
+    fn full_precision_strings() {
+        let a = FixedDecimal::<F9>::from_str("1.5").unwrap();
+        assert_eq!(a.to_string(), "1.5");
+        assert_eq!(a.to_string_full(), "1.500000000");
+        assert_eq!(format!("{:#}", a), "1.500000000");
+        let b = FixedDecimal::<F9>::from_i128(-3);
+        assert_eq!(b.to_string(), "-3");
+        assert_eq!(b.to_string_full(), "-3.000000000");
+        assert_eq!(
+            FixedDecimal::<F9>::from_str(&a.to_string_full()).unwrap(),
+            a
+        );
+    }
+
+    #[test]
+    fn display_width_and_precision() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        assert_eq!(format!("{:.2}", d("3.14159")), "3.14");
+        assert_eq!(format!("{:.2}", d("2.125")), "2.12");
+        assert_eq!(format!("{:.2}", d("-2.135")), "-2.14");
+        assert_eq!(format!("{:.0}", d("2.5")), "2");
+        assert_eq!(format!("{:.12}", d("1.5")), "1.500000000000");
+        assert_eq!(format!("{:08.2}", d("3.14159")), "00003.14");
+        assert_eq!(format!("{:08.2}", d("-3.14159")), "-0003.14");
+        assert_eq!(format!("{:+08.2}", d("3.14159")), "+0003.14");
+        assert_eq!(format!("{:06}", d("-1.5")), "-001.5");
+        assert_eq!(format!("{:03.2}", d("-1234.5")), "-1234.50");
+        assert_eq!(format!("{:>8}", d("-1.5")), "    -1.5");
+        assert_eq!(format!("{:*<8.1}", d("2.25")), "2.2*****");
+        assert_eq!(format!("{:8.2}", d("-0.001")), "    0.00");
+        assert_eq!(format!("{:08.2}", FixedDecimal::<F9>::zero()), "00000.00");
+    }
+
+    #[test]
+    fn to_string_dp() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        assert_eq!(d("0.999999").to_string_dp(2), "1.00");
+        assert_eq!(d("0.999999").to_string_dp(0), "1");
+        assert_eq!(d("-0.999999").to_string_dp(2), "-1.00");
+        assert_eq!(d("-2.345").to_string_dp(2), "-2.34");
+        assert_eq!(d("-2.355").to_string_dp(2), "-2.36");
+        assert_eq!(d("-0.004").to_string_dp(2), "0.00");
+        assert_eq!(d("1.25").to_string_dp(11), "1.25000000000");
+    }
+
+    #[test]
+    fn ipow() {
+        assert_eq!(
+            FixedDecimal::<F18>::ipow(2, 60).unwrap(),
+            FixedDecimal::from_i128(1 << 60)
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::ipow(-3, 5).unwrap(),
+            FixedDecimal::from_i128(-243)
+        );
+        assert!(matches!(
+            FixedDecimal::<F9>::ipow(10, 40),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(FixedDecimal::<F9>::ipow(10, 29).is_ok());
+        // 10^30 fits i128 but not once scaled by 10^9
+        assert!(FixedDecimal::<F9>::ipow(10, 30).is_err());
+        for base in [0, 1, -1, 7, i128::MAX, i128::MIN] {
+            assert_eq!(
+                FixedDecimal::<F18>::ipow(base, 0).unwrap(),
+                FixedDecimal::one()
+            );
+        }
+        assert_eq!(
+            FixedDecimal::<F18>::ipow(0, 3).unwrap(),
+            FixedDecimal::zero()
+        );
+    }
+
+    #[test]
+    fn to_string_min_dp() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        assert_eq!(d("1.0").to_string_min_dp(1), "1.0");
+        assert_eq!(d("1.500").to_string_min_dp(1), "1.5");
+        assert_eq!(d("42").to_string_min_dp(1), "42.0");
+        assert_eq!(d("42").to_string_min_dp(0), "42");
+        assert_eq!(d("42").to_string_min_dp(3), "42.000");
+        assert_eq!(d("-0.125").to_string_min_dp(2), "-0.125");
+        assert_eq!(d("-7.1").to_string_min_dp(2), "-7.10");
+        assert_eq!(d("0.000000001").to_string_min_dp(1), "0.000000001");
+        assert_eq!(d("2.5").to_string_min_dp(12), "2.500000000000");
+        assert_eq!(FixedDecimal::<F9>::zero().to_string_min_dp(1), "0.0");
+    }
+
+    #[test]
+    fn to_sig_figs() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        assert_eq!(d("12345").to_sig_figs(3), "1.23e4");
+        assert_eq!(d("12345").to_sig_figs(5), "12345");
+        assert_eq!(d("12345").to_sig_figs(7), "12345.00");
+        assert_eq!(d("0.0001234").to_sig_figs(2), "0.00012");
+        assert_eq!(d("0.00001234").to_sig_figs(2), "1.2e-5");
+        assert_eq!(d("-0.0001234").to_sig_figs(3), "-0.000123");
+        assert_eq!(d("-2.5").to_sig_figs(1), "-2");
+        assert_eq!(d("3.5").to_sig_figs(1), "4");
+        assert_eq!(d("9.996").to_sig_figs(3), "10.0");
+        assert_eq!(d("99.96").to_sig_figs(3), "100");
+        assert_eq!(d("999.6").to_sig_figs(3), "1.00e3");
+        assert_eq!(d("1.5").to_sig_figs(4), "1.500");
+        assert_eq!(d("0.000000001").to_sig_figs(3), "1.00e-9");
+        assert_eq!(FixedDecimal::<F9>::zero().to_sig_figs(3), "0");
+        assert_eq!(d("7.25").to_sig_figs(0), "7");
+        assert_eq!(
+            FixedDecimal::<F18>::from_raw(i128::MAX).to_sig_figs(4),
+            "1.701e20"
+        );
+    }
+
+    #[test]
+    fn percent_strings() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        for (percent, fraction) in [
+            ("2.5%", "0.025"),
+            ("-0.1%", "-0.001"),
+            ("100%", "1"),
+            ("0%", "0"),
+            ("1234.5%", "12.345"),
+            ("0.0000001%", "0.000000001"),
+        ] {
+            let value = FixedDecimal::<F9>::from_percent_str(percent).unwrap();
+            assert_eq!(value, d(fraction));
+            assert_eq!(value.to_percent_string(), percent);
+        }
+        assert_eq!(
+            FixedDecimal::<F9>::from_percent_str(" -2.5 %").unwrap(),
+            d("-0.025")
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::from_percent_str("0.00000001%").unwrap(),
+            FixedDecimal::zero()
+        );
+        assert!(matches!(
+            FixedDecimal::<F9>::from_percent_str("2.5"),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(FixedDecimal::<F9>::from_percent_str("abc%").is_err());
+        assert_eq!(d("0.5").to_percent_string(), "50%");
+        assert_eq!(
+            FixedDecimal::<F18>::from_raw(i128::MAX).to_percent_string(),
+            "17014118346046923173168.7303715884105727%"
+        );
+    }
+
+    #[test]
+    fn from_str_exact() {
+        assert_eq!(
+            FixedDecimal::<F9>::from_str_exact("-12.123456789").unwrap(),
+            FixedDecimal::from_str("-12.123456789").unwrap()
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::from_str_exact("7").unwrap(),
+            FixedDecimal::from_i128(7)
+        );
+        assert!(matches!(
+            FixedDecimal::<F9>::from_str_exact("12.1234567891"),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(FixedDecimal::<F9>::from_str_exact("0.0000000000").is_err());
+        assert!(FixedDecimal::<F18>::from_str_exact("12.1234567891").is_ok());
+        assert!(FixedDecimal::<F9>::from_str_exact("1.5x").is_err());
+    }
+
+    #[test]
+    fn from_str_lossy() {
+        let (value, lossy) = FixedDecimal::<F9>::from_str_lossy("-3.123456789").unwrap();
+        assert_eq!(value, FixedDecimal::from_str("-3.123456789").unwrap());
+        assert!(!lossy);
+        let (value, lossy) = FixedDecimal::<F9>::from_str_lossy("3.1234567891").unwrap();
+        assert_eq!(value, FixedDecimal::from_str("3.123456789").unwrap());
+        assert!(lossy);
+        assert!(
+            !FixedDecimal::<F9>::from_str_lossy("3.5000000000000")
+                .unwrap()
+                .1
+        );
+        assert!(!FixedDecimal::<F9>::from_str_lossy("42").unwrap().1);
+        assert!(
+            FixedDecimal::<F9>::from_str_lossy("0.0000000000001")
+                .unwrap()
+                .1
+        );
+        assert!(FixedDecimal::<F9>::from_str_lossy("1.5x").is_err());
+    }
+
+    #[test]
+    fn eq_str() {
+        let a = FixedDecimal::<F9>::from_str("1.5").unwrap();
+        assert!(a.eq_str("1.5"));
+        assert!(a.eq_str("1.50"));
+        assert!(a.eq_str("01.500000000"));
+        // digits past the precision are truncated, as in `from_str`
+        assert!(a.eq_str("1.5000000001"));
+        assert!(!a.eq_str("1.49"));
+        assert!(!a.eq_str("-1.5"));
+        assert!(!a.eq_str("1.5x"));
+        assert!(!a.eq_str(""));
+        assert!(FixedDecimal::<F9>::zero().eq_str("-0.0"));
+    }
+
+    #[test]
+    fn negative_zero() {
+        // digits past the precision are truncated, so the last two are zero too
+        for x in [
+            "-0",
+            "-0.0",
+            "-0.000",
+            "-00",
+            "-0.000000000",
+            "-0.0000000001",
+        ] {
+            let parsed = FixedDecimal::<F9>::from_str(x).unwrap();
+            assert_eq!(parsed, FixedDecimal::zero());
+            assert_eq!(parsed.to_raw(), 0);
+            assert_eq!(parsed.to_string(), "0");
+            assert_eq!(parsed.to_string_full(), "0.000000000");
+            assert_eq!(parsed.to_string_grouped(','), "0");
+        }
+        let tiny = FixedDecimal::<F9>::from_str("-0.000000001").unwrap();
+        assert_eq!(tiny.to_string(), "-0.000000001");
+        assert_eq!((tiny - tiny).to_string(), "0");
+        assert_eq!((-FixedDecimal::<F9>::zero()).to_string(), "0");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            FixedDecimal::<F9>::from_str("1x.5"),
+            Err(FixedFastError::DomainError("invalid integer part"))
+        ));
+        assert!(matches!(
+            FixedDecimal::<F9>::from_str("1.5x"),
+            Err(FixedFastError::DomainError("invalid decimal part"))
+        ));
+        assert!(matches!(
+            FixedDecimal::<F9>::from_str(""),
+            Err(FixedFastError::DomainError("invalid integer part"))
+        ));
+        for malformed in ["--5", "+-5", "-+5", "1-5"] {
+            assert!(matches!(
+                FixedDecimal::<F9>::from_str(malformed),
+                Err(FixedFastError::DomainError("invalid integer part"))
+            ));
+        }
+        for malformed in ["1.2.3", "1.-5", "1.+5", "1.12345678é"] {
+            assert!(matches!(
+                FixedDecimal::<F9>::from_str(malformed),
+                Err(FixedFastError::DomainError("invalid decimal part"))
+            ));
+        }
+        assert_eq!(
+            FixedDecimal::<F9>::from_str("+1.5").unwrap(),
+            FixedDecimal::<F9>::from_str("1.5").unwrap()
+        );
+        assert!(matches!(
+            FixedDecimal::<F9>::from_str("1000000000000000000000000000000"),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(matches!(
+            FixedDecimal::<F9>::from_str_grouped("1,2x3", ','),
+            Err(FixedFastError::DomainError("invalid integer part"))
+        ));
     }
 
     #[test]
@@ -337,6 +1085,562 @@ mod tests {
         let c = FixedDecimal::<F18>::from_str("1.123").unwrap();
         let d = x.polynomial(&[a, b, c]);
         assert_eq!(d, FixedDecimal::<F18>::from_str("11.073078867").unwrap());
+        let x = FixedDecimal::<F18>::from_str("0.5").unwrap();
+        let d = x.polynomial(&[FixedDecimal::<F18>::from_i128(7)]);
+        assert_eq!(d, FixedDecimal::<F18>::from_i128(7));
+        // 1 - x + x^2 - x^3 + x^4 - x^5 at x = -2 is 63
+        let coefficients: Vec<FixedDecimal<F18>> = [1, -1, 1, -1, 1, -1]
+            .iter()
+            .map(|c| FixedDecimal::from_i128(*c))
+            .collect();
+        let x = FixedDecimal::<F18>::from_i128(-2);
+        assert_eq!(
+            x.polynomial(&coefficients),
+            FixedDecimal::<F18>::from_i128(63)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "polynomial needs at least one coefficient")]
+    fn polynomial_empty() {
+        FixedDecimal::<F18>::one().polynomial(&[]);
+    }
+
+    #[test]
+    fn grouped_strings() {
+        let cases = [
+            ("1,234,567.89", "1234567.89"),
+            ("-1,234,567.89", "-1234567.89"),
+            ("123", "123"),
+            ("-999,999", "-999999"),
+            ("0.5", "0.5"),
+            ("-0.5", "-0.5"),
+            ("1,000", "1000"),
+        ];
+        for (grouped, plain) in cases {
+            let value = FixedDecimal::<F9>::from_str_grouped(grouped, ',').unwrap();
+            assert_eq!(value, FixedDecimal::<F9>::from_str(plain).unwrap());
+            assert_eq!(value.to_string_grouped(','), grouped);
+        }
+        let value = FixedDecimal::<F9>::from_str("12345.5").unwrap();
+        assert_eq!(value.to_string_grouped('_'), "12_345.5");
+        assert!(FixedDecimal::<F9>::from_str_grouped("1,2x3", ',').is_err());
+    }
+
+    #[test]
+    fn hex() {
+        let a = FixedDecimal::<F9>::from_str("1.5").unwrap();
+        assert_eq!(a.to_hex(), "00000000000000000000000059682f00");
+        assert_eq!(FixedDecimal::<F9>::from_hex(&a.to_hex()).unwrap(), a);
+        let b = FixedDecimal::<F18>::from_raw(-1);
+        assert_eq!(b.to_hex(), "ffffffffffffffffffffffffffffffff");
+        assert_eq!(FixedDecimal::<F18>::from_hex(&b.to_hex()).unwrap(), b);
+        let c = FixedDecimal::<F18>::from_raw(i128::MIN);
+        assert_eq!(FixedDecimal::<F18>::from_hex(&c.to_hex()).unwrap(), c);
+        assert!(FixedDecimal::<F9>::from_hex("59682f00").is_err());
+        assert!(FixedDecimal::<F9>::from_hex("0000000000000000000000000000000g").is_err());
+        assert!(FixedDecimal::<F9>::from_hex("+000000000000000000000000000000a").is_err());
+    }
+
+    #[test]
+    fn floor_trunc_round() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let cases = [
+            // x, floor, trunc, round
+            ("2.5", "2", "2", "2"),
+            ("-2.5", "-3", "-2", "-2"),
+            ("2.4", "2", "2", "2"),
+            ("-2.6", "-3", "-2", "-3"),
+            ("3.5", "3", "3", "4"),
+            ("-3.5", "-4", "-3", "-4"),
+            ("0.500000001", "0", "0", "1"),
+            ("-0.000000001", "-1", "0", "0"),
+            ("-7", "-7", "-7", "-7"),
+            ("0", "0", "0", "0"),
+        ];
+        for (x, floor, trunc, round) in cases {
+            let x = d(x);
+            assert_eq!(x.floor(), d(floor));
+            assert_eq!(x.floor_i128(), d(floor).to_i128());
+            assert_eq!(x.trunc(), d(trunc));
+            assert_eq!(x.round(), d(round));
+        }
+    }
+
+    #[test]
+    fn clamp_positive_negative() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let zero = FixedDecimal::<F9>::zero();
+        assert_eq!(d("-2.5").clamp_positive(), zero);
+        assert_eq!(d("-2.5").clamp_negative(), d("-2.5"));
+        assert_eq!(d("-2.5").saturating_to_zero(), zero);
+        assert_eq!(zero.clamp_positive(), zero);
+        assert_eq!(zero.clamp_negative(), zero);
+        assert_eq!(zero.saturating_to_zero(), zero);
+        assert_eq!(d("0.000000001").clamp_positive(), d("0.000000001"));
+        assert_eq!(d("3").clamp_negative(), zero);
+        assert_eq!(d("3").saturating_to_zero(), d("3"));
+    }
+
+    #[test]
+    fn is_integer() {
+        for x in ["0", "3", "-3", "1000000", "-0.000000000"] {
+            assert!(FixedDecimal::<F9>::from_str(x).unwrap().is_integer());
+        }
+        for x in ["0.5", "-0.5", "2.000000001", "-7.25", "-0.000000001"] {
+            assert!(!FixedDecimal::<F9>::from_str(x).unwrap().is_integer());
+        }
+        assert!(!FixedDecimal::<F18>::from_raw(i128::MIN).is_integer());
+    }
+
+    #[test]
+    fn currency() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        assert_eq!(
+            d("1234.5").currency("$", 2, Some(',')).to_string(),
+            "$1,234.50"
+        );
+        assert_eq!(d("-5").currency("$", 2, Some(',')).to_string(), "-$5.00");
+        assert_eq!(
+            d("1234567.891").currency("€", 2, Some('.')).to_string(),
+            "€1.234.567.89"
+        );
+        assert_eq!(d("1234.5").currency("", 0, None).to_string(), "1234");
+        assert_eq!(
+            d("999.995").currency("$", 2, Some(',')).to_string(),
+            "$1,000.00"
+        );
+        assert_eq!(d("0.125").currency("$", 2, None).to_string(), "$0.12");
+        assert_eq!(d("0.135").currency("$", 2, None).to_string(), "$0.14");
+        // nothing left after rounding, so no sign
+        assert_eq!(d("-0.001").currency("$", 2, None).to_string(), "$0.00");
+        assert_eq!(
+            d("-12.5").currency("£", 12, Some(',')).to_string(),
+            "-£12.500000000000"
+        );
+        assert_eq!(format!("{}", d("7").currency("USD ", 2, None)), "USD 7.00");
+    }
+
+    #[test]
+    fn fractional_digits() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        assert_eq!(
+            d("0.125").fractional_digits().collect::<Vec<_>>(),
+            [1, 2, 5, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            d("-37.000000009").fractional_digits().collect::<Vec<_>>(),
+            [0, 0, 0, 0, 0, 0, 0, 0, 9]
+        );
+        assert_eq!(d("-37.000000009").signum(), -1);
+        assert!(d("12").fractional_digits().all(|digit| digit == 0));
+        assert_eq!(FixedDecimal::<F18>::one().fractional_digits().count(), 18);
+        let digits: String = FixedDecimal::<F18>::from_raw(i128::MIN)
+            .fractional_digits()
+            .map(|digit| char::from(b'0' + digit))
+            .collect();
+        assert_eq!(digits, "687303715884105728");
+    }
+
+    #[test]
+    fn split() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let cases = [
+            ("2.75", "2", "0.75"),
+            ("-2.75", "-2", "-0.75"),
+            ("-0.5", "0", "-0.5"),
+            ("7", "7", "0"),
+            ("0.000000001", "0", "0.000000001"),
+        ];
+        for (x, integer, fraction) in cases {
+            let x = d(x);
+            let (whole, fract) = x.split();
+            assert_eq!((whole, fract), (d(integer), d(fraction)));
+            assert_eq!(whole, x.trunc());
+            assert_eq!(whole + fract, x);
+        }
+        let min = FixedDecimal::<F18>::from_raw(i128::MIN);
+        let (whole, fract) = min.split();
+        assert_eq!(whole + fract, min);
+    }
+
+    #[test]
+    fn ceil() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let cases = [
+            ("-1.5", -1),
+            ("1.1", 2),
+            ("1.000000001", 2),
+            ("-0.000000001", 0),
+            ("-2.999999999", -2),
+            ("4", 4),
+            ("-4", -4),
+            ("0", 0),
+        ];
+        for (x, ceil) in cases {
+            let x = d(x);
+            assert_eq!(x.ceil_i128(), ceil);
+            assert_eq!(x.ceil(), FixedDecimal::from_i128(ceil));
+            assert_eq!(x.ceil(), -(-x).floor());
+        }
+    }
+
+    #[test]
+    fn checked_from_i128() {
+        assert_eq!(
+            FixedDecimal::<F9>::checked_from_i128(-42).unwrap(),
+            FixedDecimal::from_i128(-42)
+        );
+        let max = i128::MAX / FixedDecimal::<F18>::scale();
+        assert_eq!(
+            FixedDecimal::<F18>::checked_from_i128(max).unwrap(),
+            FixedDecimal::from_i128(max)
+        );
+        assert_eq!(
+            FixedDecimal::<F18>::checked_from_i128(-max).unwrap(),
+            FixedDecimal::from_i128(-max)
+        );
+        assert!(matches!(
+            FixedDecimal::<F18>::checked_from_i128(max + 1),
+            Err(FixedFastError::Overflow)
+        ));
+        assert!(FixedDecimal::<F18>::checked_from_i128(-max - 1).is_err());
+        assert!(FixedDecimal::<F9>::checked_from_i128(i128::MAX).is_err());
+        assert!(FixedDecimal::<F9>::checked_from_i128(i128::MIN).is_err());
+    }
+
+    #[test]
+    fn scaled_units() {
+        let price = FixedDecimal::<F9>::from_str("12.345").unwrap();
+        assert_eq!(price.scaled_units(), 12_345_000_000);
+        assert_eq!(
+            FixedDecimal::<F9>::from_scaled_units(price.scaled_units()),
+            price
+        );
+        assert_eq!(price.scaled_units(), price.to_raw());
+        assert_eq!(price.to_i128(), 12);
+        let units = -ONE_SCALED_INTEGER * 3 / 2;
+        let x = FixedDecimal::<F9>::from_scaled_units(units);
+        assert_eq!(x, FixedDecimal::from_str("-1.5").unwrap());
+        assert_eq!(x.scaled_units(), units);
+    }
+
+    #[test]
+    fn sort_key() {
+        let values: Vec<FixedDecimal<F9>> = [
+            "3.5",
+            "-0.000000001",
+            "0",
+            "-12",
+            "7",
+            "0.000000001",
+            "-12.5",
+            "3.4999",
+        ]
+        .iter()
+        .map(|x| FixedDecimal::from_str(x).unwrap())
+        .chain([
+            FixedDecimal::from_raw(i128::MIN),
+            FixedDecimal::from_raw(i128::MAX),
+        ])
+        .collect();
+        let mut by_key = values.clone();
+        by_key.sort_by_key(|x| x.sort_key());
+        let mut by_ord = values;
+        by_ord.sort();
+        assert_eq!(by_key, by_ord);
+    }
+
+    #[test]
+    fn abs_diff() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        assert_eq!(d("1.5").abs_diff(d("4")), d("2.5"));
+        assert_eq!(d("4").abs_diff(d("1.5")), d("2.5"));
+        assert_eq!(d("-3.25").abs_diff(d("2")), d("5.25"));
+        assert_eq!(d("7").abs_diff(d("7")), FixedDecimal::zero());
+
+        // opposite signs whose distance is exactly the largest raw value
+        let high = FixedDecimal::<F9>::from_raw(1 << 126);
+        let low = FixedDecimal::<F9>::from_raw(-(1 << 126) + 1);
+        assert_eq!(
+            low.checked_abs_diff(high).unwrap(),
+            FixedDecimal::from_raw(i128::MAX)
+        );
+        // `MIN - 0` itself fits, but its absolute value does not
+        let min = FixedDecimal::<F9>::from_raw(i128::MIN);
+        assert!(matches!(
+            min.checked_abs_diff(FixedDecimal::zero()),
+            Err(FixedFastError::Overflow)
+        ));
+        assert_eq!(
+            min.abs_diff(FixedDecimal::zero()),
+            FixedDecimal::from_raw(i128::MAX)
+        );
+        assert!(matches!(
+            min.checked_abs_diff(FixedDecimal::from_raw(i128::MAX)),
+            Err(FixedFastError::Overflow)
+        ));
+        assert_eq!(
+            min.abs_diff(FixedDecimal::from_raw(i128::MIN + 1)),
+            FixedDecimal::from_raw(1)
+        );
+    }
+
+    #[test]
+    fn canonical_key() {
+        use std::collections::HashMap;
+
+        for s in ["1.5", "-0.000000001", "0", "-12", "1000", "3.14159"] {
+            let a = FixedDecimal::<F9>::from_str(s).unwrap();
+            let b = FixedDecimal::<F18>::from_str(s).unwrap();
+            assert_eq!(a.canonical_key(), b.canonical_key());
+        }
+        assert_eq!(
+            FixedDecimal::<F18>::from_str("1.5")
+                .unwrap()
+                .canonical_key(),
+            (15, 1)
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::from_i128(1000).canonical_key(),
+            (1000, 0)
+        );
+        assert_eq!(FixedDecimal::<F18>::zero().canonical_key(), (0, 0));
+        assert_ne!(
+            FixedDecimal::<F18>::from_str("0.0000000001")
+                .unwrap()
+                .canonical_key(),
+            FixedDecimal::<F9>::zero().canonical_key()
+        );
+
+        let mut buckets = HashMap::new();
+        *buckets
+            .entry(
+                FixedDecimal::<F9>::from_str("2.25")
+                    .unwrap()
+                    .canonical_key(),
+            )
+            .or_insert(0) += 1;
+        *buckets
+            .entry(
+                FixedDecimal::<F18>::from_str("2.250")
+                    .unwrap()
+                    .canonical_key(),
+            )
+            .or_insert(0) += 1;
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[&(225, 2)], 2);
+    }
+
+    #[test]
+    fn f64_comparisons() {
+        let a = FixedDecimal::<F9>::from_str("1.5").unwrap();
+        assert!(a == 1.5);
+        assert!(1.5 == a);
+        assert!(a < 1.75 && a > -2.0);
+        assert!(1.25 < a);
+        assert!(a.partial_cmp(&f64::NAN).is_none());
+
+        let third: FixedDecimal<F9> = FixedDecimal::one() / 3;
+        assert!(third != 1.0 / 3.0);
+        assert!(third.approx_eq_f64(1.0 / 3.0, 1e-9));
+        assert!(!third.approx_eq_f64(0.3333, 1e-9));
+        let e = FixedDecimal::<F18>::e();
+        assert!(e.approx_eq_f64(std::f64::consts::E, 1e-15));
+    }
+
+    #[test]
+    fn raw_i64() {
+        let price = FixedDecimal::<F9>::from_str("101.25").unwrap();
+        assert_eq!(price.checked_to_raw_i64().unwrap(), 101_250_000_000);
+        assert_eq!(price.to_raw_i64_saturating(), 101_250_000_000);
+        assert_eq!(FixedDecimal::<F9>::from_raw_i64(101_250_000_000), price);
+
+        for raw in [i64::MAX, i64::MIN] {
+            let x = FixedDecimal::<F9>::from_raw_i64(raw);
+            assert_eq!(x.to_raw(), raw as i128);
+            assert_eq!(x.checked_to_raw_i64().unwrap(), raw);
+            assert_eq!(x.to_raw_i64_saturating(), raw);
+        }
+        let above = FixedDecimal::<F9>::from_raw(i64::MAX as i128 + 1);
+        assert!(matches!(
+            above.checked_to_raw_i64(),
+            Err(FixedFastError::Overflow)
+        ));
+        assert_eq!(above.to_raw_i64_saturating(), i64::MAX);
+        let below = FixedDecimal::<F9>::from_raw(i64::MIN as i128 - 1);
+        assert!(below.checked_to_raw_i64().is_err());
+        assert_eq!(below.to_raw_i64_saturating(), i64::MIN);
+        assert_eq!(
+            FixedDecimal::<F18>::from_raw(i128::MIN).to_raw_i64_saturating(),
+            i64::MIN
+        );
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn json_schema() {
+        let schema = schemars::schema_for!(FixedDecimal<F9>);
+        assert_eq!(schema.get("type").unwrap(), "string");
+        assert_eq!(schema.get("pattern").unwrap(), "^-?[0-9]+(\\.[0-9]{1,9})?$");
+        assert_eq!(schema.get("examples").unwrap()[0], "-12.5");
+        assert_eq!(schema.get("title").unwrap(), "FixedDecimal9");
+    }
+
+    #[test]
+    fn deserialize_length_cap() {
+        use serde::{Deserialize, de::IntoDeserializer, de::value::Error};
+
+        let parse = |s: &str| -> Result<FixedDecimal<F9>, Error> {
+            FixedDecimal::deserialize(s.into_deserializer())
+        };
+        assert_eq!(
+            parse("-12.5").unwrap(),
+            FixedDecimal::from_str("-12.5").unwrap()
+        );
+        let longest = format!("1.{}", "0".repeat(62));
+        assert_eq!(parse(&longest).unwrap(), FixedDecimal::one());
+
+        let too_long = "9".repeat(5_000_000);
+        let error = parse(&too_long).unwrap_err();
+        assert!(error.to_string().contains("at most 64 bytes"));
+        assert!(parse(&format!("1.{}", "0".repeat(63))).is_err());
+        // a multibyte character straddling the truncation point
+        let error = parse("1.12345678é").unwrap_err();
+        assert!(error.to_string().contains("invalid decimal part"));
+
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        struct Short;
+
+        impl FixedPrecision for Short {
+            const PRECISION: u32 = 2;
+            const MAX_STR_LEN: usize = 8;
+        }
+
+        let parse = |s: &str| -> Result<FixedDecimal<Short>, Error> {
+            FixedDecimal::deserialize(s.into_deserializer())
+        };
+        assert!(parse("12345.67").is_ok());
+        assert!(parse("123456.78").is_err());
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn borsh_roundtrip() {
+        for value in [
+            FixedDecimal::<F18>::from_str("123.456").unwrap(),
+            FixedDecimal::<F18>::from_str("-0.000000000000000001").unwrap(),
+            FixedDecimal::<F18>::zero(),
+        ] {
+            let bytes = borsh::to_vec(&value).unwrap();
+            assert_eq!(bytes, value.to_le_bytes());
+            assert_eq!(
+                borsh::from_slice::<FixedDecimal<F18>>(&bytes).unwrap(),
+                value
+            );
+        }
+        assert!(borsh::from_slice::<FixedDecimal<F18>>(&[0; 15]).is_err());
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn rust_decimal_roundtrip() {
+        use rust_decimal::Decimal;
+
+        for s in ["123.456", "-0.000000001", "0", "-98765.4321", "1"] {
+            let value = FixedDecimal::<F9>::from_str(s).unwrap();
+            let decimal = value.to_rust_decimal();
+            assert_eq!(decimal, Decimal::from_str_exact(s).unwrap());
+            assert_eq!(decimal.scale(), 9);
+            assert_eq!(
+                FixedDecimal::<F9>::from_rust_decimal(decimal).unwrap(),
+                value
+            );
+        }
+        assert_eq!(
+            FixedDecimal::<F18>::from_rust_decimal(Decimal::new(-15, 1)).unwrap(),
+            FixedDecimal::<F18>::from_str("-1.5").unwrap()
+        );
+        assert!(matches!(
+            FixedDecimal::<F9>::from_rust_decimal(Decimal::new(1, 10)),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(matches!(
+            FixedDecimal::<F18>::from_rust_decimal(Decimal::MAX),
+            Err(FixedFastError::Overflow)
+        ));
+        // 1e12 at F18 needs more than 96 bits, so the last digits go
+        let large = FixedDecimal::<F18>::from_str("1000000000000.123456789123456789").unwrap();
+        assert_eq!(
+            large.to_rust_decimal(),
+            Decimal::from_str_exact("1000000000000.1234567891234567").unwrap()
+        );
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_cast() {
+        let values = [
+            FixedDecimal::<F18>::from_str("1.5").unwrap(),
+            FixedDecimal::<F18>::from_i128(-42),
+            FixedDecimal::<F18>::zero(),
+        ];
+        let bytes: &[u8] = bytemuck::cast_slice(&values);
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(&bytes[..16], &values[0].to_le_bytes());
+        let back: &[FixedDecimal<F18>] = bytemuck::cast_slice(bytes);
+        assert_eq!(back, &values);
+        assert_eq!(
+            <FixedDecimal<F18> as bytemuck::Zeroable>::zeroed(),
+            FixedDecimal::<F18>::zero()
+        );
+    }
+
+    #[test]
+    fn from_scaled() {
+        const HALF: FixedDecimal<F9> = FixedDecimal::from_scaled(0, 5, 1);
+        const COEFFICIENTS: [FixedDecimal<F9>; 13] = [
+            FixedDecimal::from_scaled(0, -436953479, 17),
+            FixedDecimal::from_scaled(1, 59576962, 8),
+            FixedDecimal::from_scaled(0, -955404601, 14),
+            FixedDecimal::from_scaled(0, 727416999, 10),
+            FixedDecimal::from_scaled(0, -265650239, 12),
+            FixedDecimal::from_scaled(0, 50857094, 11),
+            FixedDecimal::from_scaled(0, -796533855, 12),
+            FixedDecimal::from_scaled(0, 597784887, 12),
+            FixedDecimal::from_scaled(0, -400772306, 12),
+            FixedDecimal::from_scaled(0, 14965658, 11),
+            FixedDecimal::from_scaled(0, -298879607, 13),
+            FixedDecimal::from_scaled(0, 306494352, 14),
+            FixedDecimal::from_scaled(0, -127834049, 15),
+        ];
+        let parsed = [
+            "-0.00000000436953479",
+            "1.59576962000000000",
+            "-0.00000955404601",
+            "0.07274169990000000",
+            "-0.00026565023900000",
+            "0.00050857094000000",
+            "-0.00079653385500000",
+            "0.00059778488700000",
+            "-0.00040077230600000",
+            "0.00014965658000000",
+            "-0.00002988796070000",
+            "0.00000306494352000",
+            "-0.00000012783404900",
+        ];
+        assert_eq!(HALF, FixedDecimal::from_str("0.5").unwrap());
+        for (coefficient, parsed) in COEFFICIENTS.iter().zip(parsed) {
+            assert_eq!(*coefficient, FixedDecimal::from_str(parsed).unwrap());
+        }
+        assert_eq!(
+            FixedDecimal::<F18>::from_scaled(-1, -25, 2),
+            FixedDecimal::from_str("-1.25").unwrap()
+        );
+        assert_eq!(
+            FixedDecimal::<F9>::from_scaled(3, 14159265358979, 14),
+            FixedDecimal::from_str("3.14159265358979").unwrap()
+        );
     }
 
     #[test]
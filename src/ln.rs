@@ -3,9 +3,8 @@ use std::marker::PhantomData;
 use crate::{
     error::{FixedFastError, Result},
     fixed_decimal::{FixedDecimal, FixedPrecision},
-    function::{Function, TryFunction},
-    interpolation::linear_interpolation,
-    lookup_table::LookupTable,
+    function::Function,
+    lookup_table::{Hybrid, LookupTable, LookupTableBuilder},
 };
 
 pub type LnV1<T> = LnLinearInterpLookupTable<T, 12>;
@@ -20,6 +19,10 @@ impl<T: FixedPrecision, const APPROX_DEPTH: u32> Function<T>
     fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
         range_reduce_arctanh_ln::<T, APPROX_DEPTH>(x)
     }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        range_reduce_arctanh_ln_try::<T, APPROX_DEPTH>(x)
+    }
 }
 
 impl<T: FixedPrecision, const APPROX_DEPTH: u32> LnArcTanhExpansion<T, APPROX_DEPTH> {
@@ -30,6 +33,12 @@ impl<T: FixedPrecision, const APPROX_DEPTH: u32> LnArcTanhExpansion<T, APPROX_DE
     }
 }
 
+impl<T: FixedPrecision, const APPROX_DEPTH: u32> Default for LnArcTanhExpansion<T, APPROX_DEPTH> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct LnLinearInterpLookupTable<T: FixedPrecision, const APPROX_DEPTH: u32> {
     lookup: LookupTable<T>,
 }
@@ -45,27 +54,43 @@ impl<T: FixedPrecision, const APPROX_DEPTH: u32> LnLinearInterpLookupTable<T, AP
             ),
         }
     }
+
+    /// Builds the table from `builder`, whose extrapolation and interpolation
+    /// modes `evaluate` then follows.
+    pub fn from_builder(builder: LookupTableBuilder<T>) -> Result<Self> {
+        Ok(Self {
+            lookup: builder.build(range_reduce_arctanh_ln::<T, APPROX_DEPTH>)?,
+        })
+    }
+
+    /// The interpolated value with the slope of its table segment; see
+    /// `LookupTable::evaluate_with_slope`.
+    pub fn evaluate_with_slope(
+        &self,
+        x: FixedDecimal<T>,
+    ) -> Result<(FixedDecimal<T>, FixedDecimal<T>)> {
+        self.lookup.evaluate_with_slope(x)
+    }
 }
 
 impl<T: FixedPrecision, const APPROX_DEPTH: u32> Function<T>
     for LnLinearInterpLookupTable<T, APPROX_DEPTH>
 {
     fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
-        let index = self.lookup.get_index(x).expect("Index not found");
-        if index + 1 >= self.lookup.table.len() {
-            return self.lookup.table[index];
-        }
-        let lower_value = self.lookup.step_size() * index + self.lookup.start();
-        linear_interpolation(
-            x,
-            lower_value,
-            lower_value + self.lookup.step_size(),
-            self.lookup.table[index],
-            self.lookup.table[index + 1],
-        )
+        self.lookup.try_evaluate(x).expect("Index not found")
+    }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        self.lookup.try_evaluate(x)
     }
 }
 
+/// Natural log over any positive input: values between the table's first
+/// and last samples are interpolated, everything else goes to
+/// `range_reduce_arctanh_ln` at `APPROX_DEPTH`. Out-of-table inputs never
+/// panic; only non-positive ones fail.
+pub type LnHybrid<T, const APPROX_DEPTH: u32 = 20> = Hybrid<T, LnArcTanhExpansion<T, APPROX_DEPTH>>;
+
 pub fn range_reduce_arctanh_ln_try<T: FixedPrecision, const APPROX_DEPTH: u32>(
     input: FixedDecimal<T>,
 ) -> Result<FixedDecimal<T>> {
@@ -86,11 +111,11 @@ pub fn range_reduce_arctanh_ln_try<T: FixedPrecision, const APPROX_DEPTH: u32>(
     }
     let arctan_term: FixedDecimal<T> = (input - 1) / (input + 1);
     let arctan_term_squared = arctan_term * arctan_term;
-    let mut nth_term = arctan_term;
-    let mut running_sum = nth_term;
+    let mut nth_power = arctan_term;
+    let mut running_sum = nth_power;
     for n in 1..APPROX_DEPTH {
-        nth_term = nth_term * arctan_term_squared / (2 * n as i64 + 1);
-        running_sum += nth_term;
+        nth_power *= arctan_term_squared;
+        running_sum += nth_power / (2 * n as i64 + 1);
     }
     Ok(running_sum * 2 + FixedDecimal::<T>::ln2() * shift_coef)
 }
@@ -102,33 +127,6 @@ pub fn range_reduce_arctanh_ln<T: FixedPrecision, const APPROX_DEPTH: u32>(
     range_reduce_arctanh_ln_try::<T, APPROX_DEPTH>(input).expect("ln computation failed")
 }
 
-impl<T: FixedPrecision, const APPROX_DEPTH: u32> TryFunction<T>
-    for LnArcTanhExpansion<T, APPROX_DEPTH>
-{
-    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
-        range_reduce_arctanh_ln_try::<T, APPROX_DEPTH>(x)
-    }
-}
-
-impl<T: FixedPrecision, const APPROX_DEPTH: u32> TryFunction<T>
-    for LnLinearInterpLookupTable<T, APPROX_DEPTH>
-{
-    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
-        let index = self.lookup.get_index(x)?;
-        if index + 1 >= self.lookup.table.len() {
-            return Ok(self.lookup.table[index]);
-        }
-        let lower_value = self.lookup.step_size() * index + self.lookup.start();
-        Ok(linear_interpolation(
-            x,
-            lower_value,
-            lower_value + self.lookup.step_size(),
-            self.lookup.table[index],
-            self.lookup.table[index + 1],
-        ))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,26 +147,101 @@ mod tests {
         let input = FixedDecimal::<F18>::from_str("1.4").unwrap();
         assert_eq!(
             range_reduce_arctanh_ln::<F18, 10>(input),
-            FixedDecimal::<F18>::from_str("0.336436968116129286").unwrap()
+            FixedDecimal::<F18>::from_str("0.336472236621212916").unwrap()
         );
         let input = FixedDecimal::<F18>::from_str("69.3").unwrap();
         assert_eq!(
             range_reduce_arctanh_ln::<F18, 10>(input),
-            FixedDecimal::<F18>::from_str("4.238444879656876612").unwrap()
+            FixedDecimal::<F18>::from_str("4.238444906195857542").unwrap()
         );
     }
 
     #[test]
     fn test_lookup_table() {
-        // let ln = LnLinearInterpLookupTable::<F18, 10>::new(
-        //     FixedDecimal::<F18>::from_str("0.000000001").unwrap(),
-        //     FixedDecimal::<F18>::from_str("10").unwrap(),
-        //     FixedDecimal::<F18>::from_str("0.01").unwrap(),
-        // );
-        // let input = FixedDecimal::<F18>::from_str("1.3453453453453453").unwrap();
-        // assert_eq!(
-        //     ln.evaluate(input),
-        //     FixedDecimal::<F18>::from_str("0.296631876146752907").unwrap()
-        // );
+        let ln = LnLinearInterpLookupTable::<F18, 10>::new(
+            FixedDecimal::<F18>::from_str("0.000000001").unwrap(),
+            FixedDecimal::<F18>::from_str("10").unwrap(),
+            FixedDecimal::<F18>::from_str("0.01").unwrap(),
+        );
+        let input = FixedDecimal::<F18>::from_str("1.3453453453453453").unwrap();
+        // ln is concave, so the chord sits just below the series value
+        // 0.296650742434541296, by at most step^2 / (8 x^2)
+        assert_eq!(
+            ln.evaluate(input),
+            FixedDecimal::<F18>::from_str("0.296643866727735657").unwrap()
+        );
+        let exact = range_reduce_arctanh_ln::<F18, 10>(input);
+        assert!(ln.evaluate(input) < exact);
+        assert!(exact - ln.evaluate(input) < FixedDecimal::<F18>::from_str("0.00001").unwrap());
+    }
+
+    #[test]
+    fn test_lookup_table_at_end() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let ln = LnLinearInterpLookupTable::<F18, 10>::new(d("1"), d("10"), d("0.01"));
+        // the last sample is at 9.99; from there to `end` it is held, since
+        // there is no sample above it to interpolate towards
+        let last = range_reduce_arctanh_ln::<F18, 10>(d("9.99"));
+        assert_eq!(ln.evaluate(d("9.99")), last);
+        assert_eq!(ln.evaluate(d("9.995")), last);
+        assert_eq!(ln.evaluate(d("10")), last);
+        assert_eq!(
+            ln.evaluate_with_slope(d("10")).unwrap(),
+            (last, FixedDecimal::zero())
+        );
+        assert!(Function::evaluate_checked(&ln, d("10.000000000000000001")).is_err());
+    }
+
+    #[test]
+    fn test_lookup_table_try_new_with() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let ln = range_reduce_arctanh_ln_try::<F18, 10>;
+        // the first sample is ln(0)
+        assert!(matches!(
+            LookupTable::try_new_with(d("0"), d("4"), d("0.5"), ln),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(LookupTable::try_new_with(d("-3"), d("4"), d("0.5"), ln).is_err());
+
+        let table = LookupTable::try_new_with(d("0.5"), d("4"), d("0.5"), ln).unwrap();
+        let reference = LookupTable::new(
+            d("0.5"),
+            d("4"),
+            d("0.5"),
+            range_reduce_arctanh_ln::<F18, 10>,
+        );
+        assert_eq!(table.table, reference.table);
+        assert_eq!(
+            table.try_evaluate(d("1.75")).unwrap(),
+            reference.try_evaluate(d("1.75")).unwrap()
+        );
+        assert!(LookupTable::try_new_with(d("1"), d("4"), d("0"), ln).is_err());
+    }
+
+    #[test]
+    fn test_ln_hybrid() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let ln = LnHybrid::<F18>::new(d("0.5"), d("10"), d("0.001"));
+        let table = LnLinearInterpLookupTable::<F18, 20>::new(d("0.5"), d("10"), d("0.001"));
+
+        let input = d("1.3453453453453453");
+        assert!(ln.in_table(input));
+        assert_eq!(ln.evaluate(input), table.evaluate(input));
+        assert!(
+            (ln.evaluate(input) - range_reduce_arctanh_ln::<F18, 20>(input)).abs() < d("0.0000001")
+        );
+
+        for input in [d("0.1"), d("9.9995"), d("10"), d("1000"), d("0.000000001")] {
+            assert!(!ln.in_table(input));
+            assert_eq!(
+                ln.evaluate(input),
+                range_reduce_arctanh_ln::<F18, 20>(input)
+            );
+        }
+        assert!(Function::evaluate_checked(&table, d("1000")).is_err());
+        assert!(matches!(
+            Function::evaluate_checked(&ln, FixedDecimal::zero()),
+            Err(FixedFastError::DomainError(_))
+        ));
     }
 }
@@ -0,0 +1,124 @@
+use crate::{
+    cdf::CDFCustomAprox,
+    error::{FixedFastError, Result},
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    function::Function,
+    ln::range_reduce_arctanh_ln_try,
+    pdf::pdf,
+};
+
+/// Log-normal CDF, `Φ((ln x - mu) / sigma)`, which is zero for `x <= 0`.
+pub fn lognormal_cdf<T: FixedPrecision>(
+    x: FixedDecimal<T>,
+    mu: FixedDecimal<T>,
+    sigma: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    validate_sigma(sigma)?;
+    if x <= 0 {
+        return Ok(FixedDecimal::zero());
+    }
+    Ok(CDFCustomAprox::new().evaluate(standardize(x, mu, sigma)?))
+}
+
+/// Log-normal density, `φ((ln x - mu) / sigma) / (x sigma)`, which is zero
+/// for `x <= 0`.
+pub fn lognormal_pdf<T: FixedPrecision>(
+    x: FixedDecimal<T>,
+    mu: FixedDecimal<T>,
+    sigma: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    validate_sigma(sigma)?;
+    if x <= 0 {
+        return Ok(FixedDecimal::zero());
+    }
+    pdf(standardize(x, mu, sigma)?).checked_div(x.checked_mul(sigma)?)
+}
+
+fn standardize<T: FixedPrecision>(
+    x: FixedDecimal<T>,
+    mu: FixedDecimal<T>,
+    sigma: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    (range_reduce_arctanh_ln_try::<T, 20>(x)? - mu).checked_div(sigma)
+}
+
+fn validate_sigma<T: FixedPrecision>(sigma: FixedDecimal<T>) -> Result<()> {
+    if sigma <= 0 {
+        return Err(FixedFastError::DomainError(
+            "log-normal sigma must be positive",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F14;
+
+    impl FixedPrecision for F14 {
+        const PRECISION: u32 = 14;
+    }
+
+    fn d(s: &str) -> FixedDecimal<F14> {
+        FixedDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_lognormal() {
+        let tolerance = d("0.0000001");
+        let cases = [
+            ("1.5", "0", "1", "0.6574321694851541", "0.24497365171050997"),
+            (
+                "2",
+                "0.5",
+                "0.25",
+                "0.780117089512224",
+                "0.5920062897891274",
+            ),
+            (
+                "0.3",
+                "-1",
+                "0.8",
+                "0.3993746777129925",
+                "1.6090983490574846",
+            ),
+            (
+                "100",
+                "4",
+                "0.5",
+                "0.8869258439289267",
+                "0.0038356228450328335",
+            ),
+        ];
+        for (x, mu, sigma, cdf, pdf) in cases {
+            let (x, mu, sigma) = (d(x), d(mu), d(sigma));
+            assert!((lognormal_cdf(x, mu, sigma).unwrap() - d(cdf)).abs() < tolerance);
+            assert!((lognormal_pdf(x, mu, sigma).unwrap() - d(pdf)).abs() < tolerance);
+        }
+    }
+
+    #[test]
+    fn test_lognormal_domain() {
+        let (mu, sigma) = (d("0"), d("1"));
+        assert_eq!(
+            lognormal_cdf(d("0"), mu, sigma).unwrap(),
+            FixedDecimal::zero()
+        );
+        assert_eq!(
+            lognormal_cdf(d("-2"), mu, sigma).unwrap(),
+            FixedDecimal::zero()
+        );
+        assert_eq!(
+            lognormal_pdf(d("-2"), mu, sigma).unwrap(),
+            FixedDecimal::zero()
+        );
+        assert!(matches!(
+            lognormal_cdf(d("1"), mu, d("0")),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(lognormal_pdf(d("1"), mu, d("-1")).is_err());
+    }
+}
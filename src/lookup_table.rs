@@ -1,13 +1,39 @@
+use std::{borrow::Borrow, cell::Cell, marker::PhantomData};
+
 use crate::{
     error::{FixedFastError, Result},
     fixed_decimal::{FixedDecimal, FixedPrecision},
+    function::{Function, TryFunction},
+    interpolation::{linear_interpolation, linear_interpolation_with_slope},
 };
 
+/// What a table does with inputs outside `[start, end]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Extrapolation {
+    /// Fail with `OutOfRange`.
+    #[default]
+    Error,
+    /// Evaluate at the nearest bound instead.
+    Clamp,
+}
+
+/// How a table fills in values between samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Straight line between the neighbouring samples.
+    #[default]
+    Linear,
+    /// Hold the sample at or below the input.
+    Step,
+}
+
 pub struct LookupTable<T: FixedPrecision> {
     pub table: Vec<FixedDecimal<T>>,
     pub start: FixedDecimal<T>,
     pub end: FixedDecimal<T>,
     pub step_size: FixedDecimal<T>,
+    pub extrapolation: Extrapolation,
+    pub interpolation: Interpolation,
 }
 
 impl<T: FixedPrecision> LookupTable<T> {
@@ -23,22 +49,200 @@ impl<T: FixedPrecision> LookupTable<T> {
             let x = start + step_size * i;
             table.push(f(x));
         }
+        Self::from_table(table, start, end, step_size)
+    }
+
+    /// `new` for untrusted parameters: fails with `TableTooLarge` instead
+    /// of allocating when the grid has more than `max_len` samples, and
+    /// with a `DomainError` for a step that is not positive or a range
+    /// shorter than one step.
+    pub fn try_new(
+        start: FixedDecimal<T>,
+        end: FixedDecimal<T>,
+        step_size: FixedDecimal<T>,
+        max_len: usize,
+        f: impl Fn(FixedDecimal<T>) -> FixedDecimal<T>,
+    ) -> Result<Self> {
+        validate_grid(start, end, step_size)?;
+        let len = Self::estimate_len(start, end, step_size);
+        if len > max_len {
+            return Err(FixedFastError::TableTooLarge(len));
+        }
+        Ok(Self::new(start, end, step_size, f))
+    }
+
+    /// `new` for a fallible `f`, such as `ln` over a range reaching zero:
+    /// fails with the first error `f` returns while filling the table, and
+    /// with a `DomainError` for a step that is not positive or a range
+    /// shorter than one step.
+    pub fn try_new_with(
+        start: FixedDecimal<T>,
+        end: FixedDecimal<T>,
+        step_size: FixedDecimal<T>,
+        f: impl Fn(FixedDecimal<T>) -> Result<FixedDecimal<T>>,
+    ) -> Result<Self> {
+        validate_grid(start, end, step_size)?;
+        let table = (0..Self::estimate_len(start, end, step_size))
+            .map(|i| f(start + step_size * i))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::from_table(table, start, end, step_size))
+    }
+
+    /// Same table as `new`, with the samples evaluated on the rayon thread
+    /// pool.
+    #[cfg(feature = "rayon")]
+    pub fn new_parallel(
+        start: FixedDecimal<T>,
+        end: FixedDecimal<T>,
+        step_size: FixedDecimal<T>,
+        f: impl Fn(FixedDecimal<T>) -> FixedDecimal<T> + Sync,
+    ) -> Self
+    where
+        T: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        let table_size = ((end.sub(start)).div(step_size)).to_i128() as usize;
+        let table = (0..table_size)
+            .into_par_iter()
+            .map(|i| f(start + step_size * i))
+            .collect();
+        Self::from_table(table, start, end, step_size)
+    }
+
+    /// Number of samples `new` stores for this grid, `(end - start) /
+    /// step_size` rounded down, so a table's size can be checked before
+    /// building it. Zero when the range is empty or the step not positive.
+    pub const fn estimate_len(
+        start: FixedDecimal<T>,
+        end: FixedDecimal<T>,
+        step_size: FixedDecimal<T>,
+    ) -> usize {
+        let (range, step) = (end.to_raw() - start.to_raw(), step_size.to_raw());
+        if range <= 0 || step <= 0 {
+            return 0;
+        }
+        (range / step) as usize
+    }
+
+    fn from_table(
+        table: Vec<FixedDecimal<T>>,
+        start: FixedDecimal<T>,
+        end: FixedDecimal<T>,
+        step_size: FixedDecimal<T>,
+    ) -> Self {
         Self {
             table,
             start,
             end,
             step_size,
+            extrapolation: Extrapolation::default(),
+            interpolation: Interpolation::default(),
         }
     }
 
+    pub fn builder() -> LookupTableBuilder<T> {
+        LookupTableBuilder::new()
+    }
+
     pub fn get_index(&self, x: FixedDecimal<T>) -> Result<usize> {
         if x < self.start || x > self.end {
             return Err(FixedFastError::OutOfRange(x.to_i128()));
         }
-        let index = ((x.sub(self.start)).div(self.step_size)).to_i128() as usize;
+        // dividing the raw offsets gives the exact bucket, so a grid point
+        // never lands in the bucket below, and skips the `* scale` in `div`,
+        // which overflows for far-off inputs at F18
+        let index = (x.sub(self.start).to_raw() / self.step_size.to_raw()) as usize;
         Ok(index)
     }
 
+    /// Looks `x` up according to the table's extrapolation and interpolation
+    /// modes. Inputs past the last sample hold its value.
+    pub fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        let x = self.extrapolate(x);
+        let index = self.get_index(x)?;
+        Ok(self.interpolate_at(x, index))
+    }
+
+    /// `try_evaluate` together with the slope of the segment it
+    /// interpolated on, a cheap finite-difference derivative. The slope is
+    /// zero wherever the table holds a sample flat: in `Step` mode and past
+    /// the last sample. A grid point takes the slope of the segment above it.
+    pub fn evaluate_with_slope(
+        &self,
+        x: FixedDecimal<T>,
+    ) -> Result<(FixedDecimal<T>, FixedDecimal<T>)> {
+        let x = self.extrapolate(x);
+        let index = self.get_index(x)?;
+        Ok(self.interpolate_with_slope_at(x, index))
+    }
+
+    fn extrapolate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        match self.extrapolation {
+            Extrapolation::Error => x,
+            Extrapolation::Clamp => x.clamp(self.start, self.end),
+        }
+    }
+
+    // `index` must be the bucket `get_index` gives for `x`
+    fn interpolate_at(&self, x: FixedDecimal<T>, index: usize) -> FixedDecimal<T> {
+        match self.segment_at(index) {
+            // grid points return their sample without rounding through `t`
+            Some([x1, x2, y1, y2]) if x != x1 => linear_interpolation(x, x1, x2, y1, y2),
+            _ => self.table[index.min(self.table.len() - 1)],
+        }
+    }
+
+    fn interpolate_with_slope_at(
+        &self,
+        x: FixedDecimal<T>,
+        index: usize,
+    ) -> (FixedDecimal<T>, FixedDecimal<T>) {
+        match self.segment_at(index) {
+            Some([x1, x2, y1, y2]) => linear_interpolation_with_slope(x, x1, x2, y1, y2),
+            None => (self.interpolate_at(x, index), FixedDecimal::zero()),
+        }
+    }
+
+    // the `[x1, x2, y1, y2]` endpoints of bucket `index`, or `None` where
+    // the table holds its sample flat
+    fn segment_at(&self, index: usize) -> Option<[FixedDecimal<T>; 4]> {
+        if self.interpolation == Interpolation::Step {
+            return None;
+        }
+        let upper = *self.table.get(index + 1)?;
+        let lower_value = self.step_size * index + self.start;
+        Some([
+            lower_value,
+            lower_value + self.step_size,
+            self.table[index],
+            upper,
+        ])
+    }
+
+    /// Heap bytes held by the samples, not counting the table struct itself.
+    pub fn memory_bytes(&self) -> usize {
+        self.table.capacity() * std::mem::size_of::<FixedDecimal<T>>()
+    }
+
+    /// Yields each grid point `start + step_size * i` with its stored value.
+    pub fn iter(&self) -> impl Iterator<Item = (FixedDecimal<T>, FixedDecimal<T>)> + '_ {
+        self.table
+            .iter()
+            .enumerate()
+            .map(|(i, y)| (self.start + self.step_size * i, *y))
+    }
+
+    /// A table on the same grid, with the same modes, holding `f` of each
+    /// stored value. Interpolation then runs between the mapped samples,
+    /// so the result matches `f` of a lookup only where `f` is linear.
+    pub fn map(&self, f: impl Fn(FixedDecimal<T>) -> FixedDecimal<T>) -> LookupTable<T> {
+        Self {
+            table: self.table.iter().map(|y| f(*y)).collect(),
+            ..*self
+        }
+    }
+
     pub fn step_size(&self) -> FixedDecimal<T> {
         self.step_size
     }
@@ -51,3 +255,310 @@ impl<T: FixedPrecision> LookupTable<T> {
         self.end
     }
 }
+
+/// A table backed by a fallible `fallback` function: inputs between the
+/// table's first and last samples are interpolated, everything else goes to
+/// `fallback` directly. Out-of-table inputs never panic; they fail only
+/// where `fallback` does.
+pub struct Hybrid<T: FixedPrecision, F> {
+    lookup: LookupTable<T>,
+    last_sample: Option<FixedDecimal<T>>,
+    fallback: F,
+}
+
+impl<T: FixedPrecision, F: TryFunction<T>> Hybrid<T, F> {
+    /// Samples the table from `fallback` over `[start, end]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fallback` fails at any grid point.
+    pub fn with_fallback(
+        fallback: F,
+        start: FixedDecimal<T>,
+        end: FixedDecimal<T>,
+        step_size: FixedDecimal<T>,
+    ) -> Self {
+        let lookup = LookupTable::new(start, end, step_size, |x| {
+            fallback
+                .try_evaluate(x)
+                .expect("fallback failed on the table grid")
+        });
+        // past the last sample the table holds it flat, so the fallback takes over
+        let last_sample = lookup.iter().last().map(|(x, _)| x);
+        Self {
+            lookup,
+            last_sample,
+            fallback,
+        }
+    }
+
+    /// Whether `x` is answered from the table rather than by the fallback.
+    pub fn in_table(&self, x: FixedDecimal<T>) -> bool {
+        self.last_sample
+            .is_some_and(|last| self.lookup.start() <= x && x <= last)
+    }
+
+    pub fn lookup(&self) -> &LookupTable<T> {
+        &self.lookup
+    }
+
+    pub fn fallback(&self) -> &F {
+        &self.fallback
+    }
+}
+
+impl<T: FixedPrecision, F: TryFunction<T> + Default> Hybrid<T, F> {
+    /// `with_fallback` for a fallback that needs no configuration.
+    pub fn new(start: FixedDecimal<T>, end: FixedDecimal<T>, step_size: FixedDecimal<T>) -> Self {
+        Self::with_fallback(F::default(), start, end, step_size)
+    }
+}
+
+impl<T: FixedPrecision, F: TryFunction<T>> Function<T> for Hybrid<T, F> {
+    fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        self.evaluate_checked(x).expect("hybrid computation failed")
+    }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        if self.in_table(x) {
+            self.lookup.try_evaluate(x)
+        } else {
+            self.fallback.try_evaluate(x)
+        }
+    }
+}
+
+/// Wraps a lookup table, owned or borrowed, and remembers the bucket of the
+/// last query. A query landing in the same bucket reuses it instead of
+/// calling `get_index`, which pays off for streams where consecutive inputs
+/// are close together. Results are identical to the table's own. The cache
+/// is a `Cell`, so a `Local` is meant to stay on one thread.
+pub struct Local<T: FixedPrecision, F: Borrow<LookupTable<T>>> {
+    table: F,
+    last_index: Cell<Option<usize>>,
+    _precision: PhantomData<T>,
+}
+
+impl<T: FixedPrecision, F: Borrow<LookupTable<T>>> Local<T, F> {
+    pub fn new(table: F) -> Self {
+        Self {
+            table,
+            last_index: Cell::new(None),
+            _precision: PhantomData,
+        }
+    }
+
+    pub fn inner(&self) -> &F {
+        &self.table
+    }
+
+    /// `LookupTable::evaluate_with_slope` through the cache.
+    pub fn evaluate_with_slope(
+        &self,
+        x: FixedDecimal<T>,
+    ) -> Result<(FixedDecimal<T>, FixedDecimal<T>)> {
+        let table = self.table.borrow();
+        let x = table.extrapolate(x);
+        let index = self.cached_index(table, x)?;
+        Ok(table.interpolate_with_slope_at(x, index))
+    }
+
+    // `x` must already be extrapolated
+    fn cached_index(&self, table: &LookupTable<T>, x: FixedDecimal<T>) -> Result<usize> {
+        match self.last_index.get() {
+            Some(index) if Self::in_bucket(table, x, index) => Ok(index),
+            _ => {
+                let index = table.get_index(x)?;
+                self.last_index.set(Some(index));
+                Ok(index)
+            }
+        }
+    }
+
+    // whether `get_index(x)` would return `index`, compared on raw values
+    // exactly as `get_index` divides them
+    fn in_bucket(table: &LookupTable<T>, x: FixedDecimal<T>, index: usize) -> bool {
+        let lower = (table.start + table.step_size * index).to_raw();
+        let x = x.to_raw();
+        x >= lower && x - lower < table.step_size.to_raw() && x <= table.end.to_raw()
+    }
+}
+
+impl<T: FixedPrecision, F: Borrow<LookupTable<T>>> Function<T> for Local<T, F> {
+    fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        self.evaluate_checked(x).expect("Index not found")
+    }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        let table = self.table.borrow();
+        let x = table.extrapolate(x);
+        let index = self.cached_index(table, x)?;
+        Ok(table.interpolate_at(x, index))
+    }
+}
+
+/// Named-field alternative to the positional `LookupTable::new` arguments.
+/// `start` defaults to zero; `end` and `step_size` must be set before
+/// calling `build`.
+#[derive(Debug, Clone, Copy)]
+pub struct LookupTableBuilder<T: FixedPrecision> {
+    pub(crate) start: FixedDecimal<T>,
+    end: Option<FixedDecimal<T>>,
+    step_size: Option<FixedDecimal<T>>,
+    extrapolation: Extrapolation,
+    interpolation: Interpolation,
+}
+
+impl<T: FixedPrecision> LookupTableBuilder<T> {
+    pub fn new() -> Self {
+        Self {
+            start: FixedDecimal::zero(),
+            end: None,
+            step_size: None,
+            extrapolation: Extrapolation::default(),
+            interpolation: Interpolation::default(),
+        }
+    }
+
+    pub fn start(mut self, start: FixedDecimal<T>) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn end(mut self, end: FixedDecimal<T>) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    pub fn step_size(mut self, step_size: FixedDecimal<T>) -> Self {
+        self.step_size = Some(step_size);
+        self
+    }
+
+    pub fn extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Samples `f` over the configured grid, failing with `DomainError` when
+    /// `end` or `step_size` is missing, the step is not positive, or the
+    /// range is shorter than one step.
+    pub fn build(&self, f: impl Fn(FixedDecimal<T>) -> FixedDecimal<T>) -> Result<LookupTable<T>> {
+        let end = self
+            .end
+            .ok_or(FixedFastError::DomainError("lookup table end is not set"))?;
+        let step_size = self.step_size.ok_or(FixedFastError::DomainError(
+            "lookup table step size is not set",
+        ))?;
+        validate_grid(self.start, end, step_size)?;
+        let mut table = LookupTable::new(self.start, end, step_size, f);
+        table.extrapolation = self.extrapolation;
+        table.interpolation = self.interpolation;
+        Ok(table)
+    }
+}
+
+fn validate_grid<T: FixedPrecision>(
+    start: FixedDecimal<T>,
+    end: FixedDecimal<T>,
+    step_size: FixedDecimal<T>,
+) -> Result<()> {
+    if step_size <= FixedDecimal::zero() {
+        return Err(FixedFastError::DomainError(
+            "lookup table step size must be positive",
+        ));
+    }
+    if end - start < step_size {
+        return Err(FixedFastError::DomainError(
+            "lookup table range is shorter than one step",
+        ));
+    }
+    Ok(())
+}
+
+impl<T: FixedPrecision> Default for LookupTableBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial::Polynomial;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    fn d(s: &str) -> FixedDecimal<F18> {
+        FixedDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_local_matches_table() {
+        let table = LookupTable::new(d("0"), d("10"), d("0.25"), |x| x * x);
+        let local = Local::new(&table);
+        // a tick stream that stays in one bucket for a while, then moves
+        let ticks = [
+            "1.3", "1.31", "1.3", "1.25", "1.49", "1.5", "1.2499", "0", "9.75", "9.9", "10", "9.8",
+            "3.333",
+        ];
+        for tick in ticks {
+            let x = d(tick);
+            assert_eq!(
+                local.evaluate_with_slope(x).unwrap(),
+                table.evaluate_with_slope(x).unwrap()
+            );
+            assert_eq!(local.evaluate(x), table.try_evaluate(x).unwrap());
+        }
+        assert!(local.evaluate_checked(d("10.01")).is_err());
+        assert!(local.evaluate_checked(d("-0.01")).is_err());
+        assert_eq!(
+            local.evaluate(d("1.3")),
+            table.try_evaluate(d("1.3")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_local_clamped_step_table() {
+        let table = LookupTable::builder()
+            .end(d("4"))
+            .step_size(d("0.5"))
+            .extrapolation(Extrapolation::Clamp)
+            .interpolation(Interpolation::Step)
+            .build(|x| x + 1)
+            .unwrap();
+        let local = Local::new(table);
+        for tick in ["0.7", "0.9", "-3", "-1", "4", "7", "3.6", "0.5"] {
+            let x = d(tick);
+            assert_eq!(
+                local.evaluate_checked(x).unwrap(),
+                local.inner().try_evaluate(x).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_hybrid_with_fallback() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        // x^2 + 1
+        let square = Polynomial::new(vec![d("1"), d("0"), d("1")]);
+        let hybrid = Hybrid::with_fallback(square.clone(), d("0"), d("2"), d("0.5"));
+        assert!(hybrid.in_table(d("1.5")));
+        assert!(!hybrid.in_table(d("1.75")));
+        assert!(!hybrid.in_table(d("-1")));
+        assert_eq!(hybrid.evaluate(d("1")), d("2"));
+        assert_eq!(hybrid.evaluate(d("0.25")), d("1.125"));
+        assert_eq!(hybrid.evaluate(d("1.75")), square.evaluate(d("1.75")));
+        assert_eq!(hybrid.evaluate(d("-3")), d("10"));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    error::{FixedFastError, Result},
+    exp::range_reduce_taylor_exp,
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    function::IntFunction,
+};
+
+/// Poisson probability mass `lambda^k e^-lambda / k!`.
+///
+/// The power and factorial are built up one `lambda / i` factor at a time,
+/// and `e^-lambda` is folded in chunks of at most one whenever the running
+/// product exceeds one. The product therefore never exceeds `lambda`, and the
+/// exponential does not underflow to zero for rates where the mass is still
+/// representable. Each chunk costs a truncation, so the relative error grows
+/// with `lambda` by roughly one ulp per unit of rate.
+pub fn poisson_pmf<T: FixedPrecision>(lambda: FixedDecimal<T>, k: u32) -> Result<FixedDecimal<T>> {
+    if lambda < 0 {
+        return Err(FixedFastError::DomainError(
+            "poisson rate must be non-negative",
+        ));
+    }
+    let one = FixedDecimal::<T>::one();
+    let mut remaining = lambda;
+    let mut pmf = one;
+    for i in 1..=k {
+        pmf = pmf
+            .checked_mul(lambda)?
+            .checked_div(FixedDecimal::from_i128(i as i128))?;
+        while pmf > one && remaining > 0 {
+            let chunk = remaining.min(one);
+            pmf = pmf.checked_mul(range_reduce_taylor_exp::<T, 20>(-chunk))?;
+            remaining -= chunk;
+        }
+        if pmf == 0 {
+            return Ok(pmf);
+        }
+    }
+    if remaining > 3 * T::PRECISION as i128 {
+        // e^-remaining is below one ulp
+        return Ok(FixedDecimal::zero());
+    }
+    pmf.checked_mul(range_reduce_taylor_exp::<T, 20>(-remaining))
+}
+
+/// `poisson_pmf` for a fixed rate as an `IntFunction` of `k`, which is zero
+/// for negative `k`.
+#[derive(Debug, Clone, Copy)]
+pub struct Poisson<T: FixedPrecision> {
+    lambda: FixedDecimal<T>,
+}
+
+impl<T: FixedPrecision> Poisson<T> {
+    pub fn new(lambda: FixedDecimal<T>) -> Result<Self> {
+        if lambda < 0 {
+            return Err(FixedFastError::DomainError(
+                "poisson rate must be non-negative",
+            ));
+        }
+        Ok(Self { lambda })
+    }
+
+    pub fn lambda(&self) -> FixedDecimal<T> {
+        self.lambda
+    }
+}
+
+impl<T: FixedPrecision> IntFunction<T> for Poisson<T> {
+    fn evaluate_int(&self, k: i128) -> FixedDecimal<T> {
+        self.try_evaluate_int(k).expect("poisson mass overflowed")
+    }
+
+    fn try_evaluate_int(&self, k: i128) -> Result<FixedDecimal<T>> {
+        match u32::try_from(k) {
+            Ok(k) => poisson_pmf(self.lambda, k),
+            // negative counts have no mass, and counts beyond u32::MAX have
+            // mass far below one ulp
+            Err(_) => Ok(FixedDecimal::zero()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    fn d(s: &str) -> FixedDecimal<F18> {
+        FixedDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_poisson_pmf() {
+        let tolerance = d("0.000000000001");
+        let cases = [
+            ("2", 3, "0.1804470443154836"),
+            ("0.5", 0, "0.6065306597126334"),
+            ("4", 4, "0.19536681481316456"),
+            ("1", 1, "0.36787944117144233"),
+        ];
+        for (lambda, k, expected) in cases {
+            let pmf = poisson_pmf(d(lambda), k).unwrap();
+            assert!((pmf - d(expected)).abs() < tolerance);
+        }
+        assert_eq!(
+            poisson_pmf(FixedDecimal::<F18>::zero(), 0).unwrap(),
+            FixedDecimal::one()
+        );
+        assert_eq!(
+            poisson_pmf(FixedDecimal::<F18>::zero(), 2).unwrap(),
+            FixedDecimal::zero()
+        );
+        assert!(matches!(
+            poisson_pmf(d("-1"), 1),
+            Err(FixedFastError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_poisson_int_function() {
+        let poisson = Poisson::new(d("2")).unwrap();
+        for k in 0..10 {
+            assert_eq!(
+                poisson.evaluate_int(k),
+                poisson_pmf(d("2"), k as u32).unwrap()
+            );
+        }
+        let total = (0..40).fold(FixedDecimal::zero(), |sum, k| sum + poisson.evaluate_int(k));
+        assert!((total - FixedDecimal::one()).abs() < d("0.000000000001"));
+        assert_eq!(poisson.evaluate_int(-1), FixedDecimal::zero());
+        assert!(matches!(
+            Poisson::new(d("-0.5")),
+            Err(FixedFastError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_poisson_pmf_large_rate_and_count() {
+        // e^-100 alone is far below one ulp at F9
+        let pmf = poisson_pmf(FixedDecimal::<F9>::from_i128(100), 100).unwrap();
+        assert!(
+            (pmf - FixedDecimal::from_str("0.039860997").unwrap()).abs()
+                < FixedDecimal::from_str("0.0000001").unwrap()
+        );
+        let pmf = poisson_pmf(d("30"), 30).unwrap();
+        assert!((pmf - d("0.07263452647159149")).abs() < d("0.0000000001"));
+        // 100^1000 alone would overflow before e^-100 is applied
+        assert_eq!(poisson_pmf(d("100"), 1000).unwrap(), FixedDecimal::zero());
+        assert_eq!(poisson_pmf(d("3"), 40).unwrap(), FixedDecimal::zero());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    error::Result,
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    function::Function,
+};
+
+/// `c0 + c1*x + c2*x^2 + ...` as a `Function`, so a polynomial can go
+/// anywhere a function is accepted. Coefficients are stored lowest degree
+/// first, the same order `FixedDecimal::polynomial` takes, and evaluated by
+/// Horner's method. With no coefficients it is the zero polynomial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial<T: FixedPrecision> {
+    coefficients: Vec<FixedDecimal<T>>,
+}
+
+impl<T: FixedPrecision> Polynomial<T> {
+    pub fn new(coefficients: Vec<FixedDecimal<T>>) -> Self {
+        Self { coefficients }
+    }
+
+    pub fn coefficients(&self) -> &[FixedDecimal<T>] {
+        &self.coefficients
+    }
+}
+
+impl<T: FixedPrecision> Function<T> for Polynomial<T> {
+    fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        if self.coefficients.is_empty() {
+            return FixedDecimal::zero();
+        }
+        x.polynomial(&self.coefficients)
+    }
+
+    /// Horner's method with checked steps, failing with `Overflow` instead
+    /// of wrapping.
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        self.coefficients
+            .iter()
+            .rev()
+            .try_fold(FixedDecimal::zero(), |result, coefficient| {
+                result.checked_mul(x)?.checked_add(*coefficient)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::FixedFastError;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    fn d(s: &str) -> FixedDecimal<F18> {
+        FixedDecimal::from_str(s).unwrap()
+    }
+
+    // composite Simpson's rule, exact for cubics up to truncation
+    fn simpson(
+        f: &impl Function<F18>,
+        a: FixedDecimal<F18>,
+        b: FixedDecimal<F18>,
+        step: FixedDecimal<F18>,
+    ) -> FixedDecimal<F18> {
+        let samples: Vec<_> = f.sample_range(a, b, step / 2).collect();
+        let mut sum = samples[0].1 + samples[samples.len() - 1].1;
+        for (i, (_, y)) in samples.iter().enumerate().take(samples.len() - 1).skip(1) {
+            sum += *y * if i % 2 == 1 { 4 } else { 2 };
+        }
+        sum * step / 6
+    }
+
+    #[test]
+    fn test_polynomial_function() {
+        // 1 - 2x + 3x^3
+        let p = Polynomial::new(vec![d("1"), d("-2"), d("0"), d("3")]);
+        assert_eq!(p.evaluate(d("2")), d("21"));
+        assert_eq!(p.evaluate(d("-0.5")), d("1.625"));
+        assert_eq!(Function::evaluate_checked(&p, d("2")).unwrap(), d("21"));
+        assert_eq!(p.evaluate(d("0.3")), d("0.3").polynomial(p.coefficients()));
+        assert_eq!(
+            Polynomial::<F18>::new(vec![]).evaluate(d("5")),
+            FixedDecimal::zero()
+        );
+        let steep = Polynomial::new(vec![d("0"), d("0"), d("0"), d("0"), d("1")]);
+        assert!(matches!(
+            Function::evaluate_checked(&steep, d("1000000")),
+            Err(FixedFastError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_integrate_polynomial() {
+        // the antiderivative is x - x^2 + 0.75x^4, so the integral over
+        // [0, 2] is 2 - 4 + 12 = 10
+        let p = Polynomial::new(vec![d("1"), d("-2"), d("0"), d("3")]);
+        let integral = simpson(&p, d("0"), d("2"), d("0.125"));
+        assert!((integral - d("10")).abs() < d("0.000000000000001"));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    error::{FixedFastError, Result},
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+};
+
+/// Opt-in wrapper for a chain of operations that records whether any
+/// intermediate result fell below `min_digits` significant digits, as
+/// measured by `debug_significant_digits`. An exact zero counts as
+/// collapsed, since it usually means cancellation consumed every digit.
+#[derive(Debug, Clone, Copy)]
+pub struct PrecisionGuard<T: FixedPrecision> {
+    value: FixedDecimal<T>,
+    min_digits: u32,
+    lowest_digits: u32,
+}
+
+impl<T: FixedPrecision> PrecisionGuard<T> {
+    pub fn new(value: FixedDecimal<T>, min_digits: u32) -> Self {
+        Self {
+            value,
+            min_digits,
+            lowest_digits: value.debug_significant_digits(),
+        }
+    }
+
+    /// Applies one step of the chain.
+    pub fn map(self, op: impl FnOnce(FixedDecimal<T>) -> FixedDecimal<T>) -> Self {
+        self.record(op(self.value))
+    }
+
+    /// Applies one fallible step, such as `checked_div`.
+    pub fn try_map(
+        self,
+        op: impl FnOnce(FixedDecimal<T>) -> Result<FixedDecimal<T>>,
+    ) -> Result<Self> {
+        Ok(self.record(op(self.value)?))
+    }
+
+    pub fn value(&self) -> FixedDecimal<T> {
+        self.value
+    }
+
+    /// Fewest significant digits seen at any step, the start included.
+    pub fn lowest_digits(&self) -> u32 {
+        self.lowest_digits
+    }
+
+    /// Whether any step left fewer than `min_digits` significant digits.
+    pub fn collapsed(&self) -> bool {
+        self.lowest_digits < self.min_digits
+    }
+
+    /// The final value, or a `DomainError` if precision collapsed along the
+    /// way, even if later steps scaled the value back up.
+    pub fn finish(self) -> Result<FixedDecimal<T>> {
+        if self.collapsed() {
+            return Err(FixedFastError::DomainError(
+                "result lost too many significant digits",
+            ));
+        }
+        Ok(self.value)
+    }
+
+    fn record(self, value: FixedDecimal<T>) -> Self {
+        Self {
+            value,
+            min_digits: self.min_digits,
+            lowest_digits: self.lowest_digits.min(value.debug_significant_digits()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    fn d(s: &str) -> FixedDecimal<F18> {
+        FixedDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_divides_collapse_precision() {
+        let third = d("1") / d("3");
+        assert_eq!(third.debug_significant_digits(), 18);
+
+        let mut guard = PrecisionGuard::new(third, 6);
+        let mut digits = vec![];
+        for _ in 0..5 {
+            guard = guard.try_map(|x| x.checked_div(d("1000"))).unwrap();
+            digits.push(guard.value().debug_significant_digits());
+        }
+        assert_eq!(digits, [15, 12, 9, 6, 3]);
+        assert!(guard.collapsed());
+        assert_eq!(guard.lowest_digits(), 3);
+        // scaling back up does not bring the lost digits back
+        let restored = guard.map(|x| x * 1_000_000_000_000_000i128);
+        assert_eq!(restored.value(), d("0.333"));
+        assert!(matches!(
+            restored.finish(),
+            Err(FixedFastError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_healthy_chain() {
+        let guard = PrecisionGuard::new(d("2"), 6)
+            .map(|x| x / d("3"))
+            .try_map(|x| x.checked_mul(d("1.5")))
+            .unwrap();
+        assert!(!guard.collapsed());
+        assert_eq!(guard.finish().unwrap(), d("0.999999999999999999"));
+        assert!(PrecisionGuard::new(d("5"), 1).map(|x| x - x).collapsed());
+        assert_eq!(FixedDecimal::<F18>::zero().debug_significant_digits(), 0);
+        assert_eq!(d("-0.000000000000000001").debug_significant_digits(), 1);
+    }
+}
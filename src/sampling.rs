@@ -0,0 +1,57 @@
+use rand::RngCore;
+
+use crate::{
+    cdf::probit,
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+};
+
+/// Draws a uniform value strictly inside `(0, 1)` on the fixed grid. Needs
+/// `T::PRECISION >= 1` so that such a value exists.
+pub fn sample_open_unit<T: FixedPrecision, R: RngCore>(rng: &mut R) -> FixedDecimal<T> {
+    let bits = ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128;
+    let interior_points = FixedDecimal::<T>::scale() as u128 - 1;
+    FixedDecimal::from_raw((1 + bits % interior_points) as i128)
+}
+
+/// Draws a standard normal variate by inverse transform sampling: a uniform
+/// in `(0, 1)` is mapped through `probit`. No floating point is involved, so
+/// a seeded RNG yields the same sequence on every platform.
+pub fn sample_standard_normal<T: FixedPrecision, R: RngCore>(rng: &mut R) -> FixedDecimal<T> {
+    probit(sample_open_unit::<T, R>(rng)).expect("uniform sample lies inside (0, 1)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    #[test]
+    fn test_sample_standard_normal_is_reproducible() {
+        let mut first = StdRng::seed_from_u64(42);
+        let mut second = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            assert_eq!(
+                sample_standard_normal::<F9, _>(&mut first),
+                sample_standard_normal::<F9, _>(&mut second)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sample_standard_normal_mean() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let draws = 5000;
+        let sum: FixedDecimal<F9> = (0..draws)
+            .map(|_| sample_standard_normal::<F9, _>(&mut rng))
+            .sum();
+        let mean: FixedDecimal<F9> = sum / draws;
+        assert!(mean.abs() < FixedDecimal::from_str("0.05").unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    error::{FixedFastError, Result},
+    exp::range_reduce_taylor_exp,
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    ln::range_reduce_arctanh_ln_try,
+};
+
+/// Computes `ln(sum(exp(x_i)))` without overflowing the exponentials by
+/// shifting every input by the maximum before exponentiating.
+pub fn log_sum_exp<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<FixedDecimal<T>> {
+    let max = *xs.iter().max().ok_or(FixedFastError::DomainError(
+        "log_sum_exp is undefined for an empty slice",
+    ))?;
+    let sum: FixedDecimal<T> = xs.iter().map(|x| shifted_exp(*x, max)).sum();
+    Ok(max + range_reduce_arctanh_ln_try::<T, 20>(sum)?)
+}
+
+/// Normalizes logits into probabilities that sum to one (up to truncation),
+/// subtracting the maximum logit before exponentiating.
+pub fn softmax<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<Vec<FixedDecimal<T>>> {
+    let max = *xs.iter().max().ok_or(FixedFastError::DomainError(
+        "softmax is undefined for an empty slice",
+    ))?;
+    let exps: Vec<FixedDecimal<T>> = xs.iter().map(|x| shifted_exp(*x, max)).collect();
+    let sum: FixedDecimal<T> = exps.iter().sum();
+    exps.into_iter().map(|e| e.checked_div(sum)).collect()
+}
+
+// exp(x - max), flushing terms that are below one ulp to zero so the range
+// reduction never has to shift by more than the width of the backing integer
+fn shifted_exp<T: FixedPrecision>(x: FixedDecimal<T>, max: FixedDecimal<T>) -> FixedDecimal<T> {
+    let diff = x - max;
+    if diff < -3 * T::PRECISION as i128 {
+        return FixedDecimal::zero();
+    }
+    range_reduce_taylor_exp::<T, 20>(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    #[test]
+    fn test_log_sum_exp() {
+        let xs = [
+            FixedDecimal::<F18>::from_str("1").unwrap(),
+            FixedDecimal::<F18>::from_str("-0.5").unwrap(),
+            FixedDecimal::<F18>::from_str("2.25").unwrap(),
+        ];
+        let direct = range_reduce_arctanh_ln_try::<F18, 20>(
+            xs.iter()
+                .map(|x| range_reduce_taylor_exp::<F18, 20>(*x))
+                .sum(),
+        )
+        .unwrap();
+        let stable = log_sum_exp(&xs).unwrap();
+        assert!((stable - direct).abs() < FixedDecimal::<F18>::from_str("0.000000000001").unwrap());
+        assert!(log_sum_exp::<F18>(&[]).is_err());
+    }
+
+    #[test]
+    fn test_log_sum_exp_large_inputs() {
+        let xs = [
+            FixedDecimal::<F9>::from_i128(1000),
+            FixedDecimal::<F9>::from_i128(1000),
+            FixedDecimal::<F9>::from_i128(-1000),
+        ];
+        let expected = FixedDecimal::<F9>::from_str("1000.693147180").unwrap();
+        assert!(
+            (log_sum_exp(&xs).unwrap() - expected).abs()
+                < FixedDecimal::<F9>::from_str("0.00000001").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_softmax() {
+        let tolerance = FixedDecimal::<F9>::from_str("0.00000001").unwrap();
+        let probabilities = softmax(&[
+            FixedDecimal::<F9>::from_i128(1),
+            FixedDecimal::<F9>::from_i128(2),
+        ])
+        .unwrap();
+        assert!(
+            (probabilities[0] - FixedDecimal::from_str("0.268941421").unwrap()).abs() < tolerance
+        );
+        assert!(
+            (probabilities[1] - FixedDecimal::from_str("0.731058579").unwrap()).abs() < tolerance
+        );
+
+        let logits = [
+            FixedDecimal::<F9>::from_str("3.2").unwrap(),
+            FixedDecimal::<F9>::from_str("-1.7").unwrap(),
+            FixedDecimal::<F9>::from_str("0.4").unwrap(),
+            FixedDecimal::<F9>::from_i128(500),
+        ];
+        let probabilities = softmax(&logits).unwrap();
+        let total: FixedDecimal<F9> = probabilities.iter().sum();
+        assert!((total - FixedDecimal::one()).abs() < tolerance);
+        assert!(softmax::<F9>(&[]).is_err());
+    }
+}
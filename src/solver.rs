@@ -0,0 +1,124 @@
+use crate::{
+    error::{FixedFastError, Result},
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+};
+
+/// Finds a root of `f` inside `[lo, hi]` by repeated halving of the bracket.
+///
+/// `f(lo)` and `f(hi)` must have opposite signs (or one of them must be zero),
+/// otherwise a `DomainError` is returned. Iteration stops once the bracket is
+/// no wider than `tolerance`, once the midpoint stops moving, or after
+/// `max_iterations` halvings.
+pub fn bisection<T: FixedPrecision>(
+    f: impl Fn(FixedDecimal<T>) -> Result<FixedDecimal<T>>,
+    lo: FixedDecimal<T>,
+    hi: FixedDecimal<T>,
+    tolerance: FixedDecimal<T>,
+    max_iterations: u32,
+) -> Result<FixedDecimal<T>> {
+    let (mut lo, mut hi) = if lo <= hi { (lo, hi) } else { (hi, lo) };
+    let mut f_lo = f(lo)?;
+    let f_hi = f(hi)?;
+    if f_lo == 0 {
+        return Ok(lo);
+    }
+    if f_hi == 0 {
+        return Ok(hi);
+    }
+    if f_lo.signum() == f_hi.signum() {
+        return Err(FixedFastError::DomainError("root is not bracketed"));
+    }
+    for _ in 0..max_iterations {
+        let mid = lo + (hi - lo) / 2;
+        if hi - lo <= tolerance || mid == lo || mid == hi {
+            return Ok(mid);
+        }
+        let f_mid = f(mid)?;
+        if f_mid == 0 {
+            return Ok(mid);
+        }
+        if f_mid.signum() == f_lo.signum() {
+            lo = mid;
+            f_lo = f_mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(lo + (hi - lo) / 2)
+}
+
+/// Finds a root of `f` by Newton-Raphson iteration starting from `x0`, using
+/// `df` as the derivative.
+///
+/// Returns once successive iterates differ by no more than `tolerance`. Fails
+/// with `DivideByZero` if the derivative vanishes and with `DomainError` if
+/// the iteration has not converged after `max_iterations` steps.
+pub fn newton_raphson<T: FixedPrecision>(
+    f: impl Fn(FixedDecimal<T>) -> Result<FixedDecimal<T>>,
+    df: impl Fn(FixedDecimal<T>) -> Result<FixedDecimal<T>>,
+    x0: FixedDecimal<T>,
+    tolerance: FixedDecimal<T>,
+    max_iterations: u32,
+) -> Result<FixedDecimal<T>> {
+    let mut x = x0;
+    for _ in 0..max_iterations {
+        let step = f(x)?.checked_div(df(x)?)?;
+        x = x.checked_sub(step)?;
+        if step.abs() <= tolerance {
+            return Ok(x);
+        }
+    }
+    Err(FixedFastError::DomainError(
+        "newton iteration did not converge",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    #[test]
+    fn test_bisection() {
+        // x^2 - 2 has a root at sqrt(2)
+        let root = bisection(
+            |x: FixedDecimal<F9>| Ok(x.squared() - 2),
+            FixedDecimal::zero(),
+            FixedDecimal::from_i128(2),
+            FixedDecimal::min_positive(),
+            200,
+        )
+        .unwrap();
+        assert_eq!(root, FixedDecimal::<F9>::from_str("1.414213562").unwrap());
+        assert!(
+            bisection(
+                |x: FixedDecimal<F9>| Ok(x.squared() + 1),
+                FixedDecimal::zero(),
+                FixedDecimal::from_i128(2),
+                FixedDecimal::min_positive(),
+                200,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_newton_raphson() {
+        let root = newton_raphson(
+            |x: FixedDecimal<F9>| Ok(x.squared() - 2),
+            |x: FixedDecimal<F9>| Ok(x * 2),
+            FixedDecimal::one(),
+            FixedDecimal::min_positive(),
+            50,
+        )
+        .unwrap();
+        // truncating division lands Newton one ulp above the bisection root
+        assert_eq!(root, FixedDecimal::<F9>::from_str("1.414213563").unwrap());
+    }
+}
@@ -3,9 +3,9 @@ use std::marker::PhantomData;
 use crate::{
     error::{FixedFastError, Result},
     fixed_decimal::{FixedDecimal, FixedPrecision},
-    function::{Function, TryFunction},
-    interpolation::linear_interpolation,
-    lookup_table::LookupTable,
+    function::Function,
+    lookup_table::{Hybrid, LookupTable, LookupTableBuilder},
+    wide::mul_div,
 };
 
 pub type SqrtV1<T> = SqrtLinearInterpLookupTable<T, 12>;
@@ -22,12 +22,54 @@ impl<T: FixedPrecision, const APPROX_DEPTH: u32> SqrtNewtonRaphson<T, APPROX_DEP
     }
 }
 
+impl<T: FixedPrecision, const APPROX_DEPTH: u32> Default for SqrtNewtonRaphson<T, APPROX_DEPTH> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: FixedPrecision, const APPROX_DEPTH: u32> Function<T>
     for SqrtNewtonRaphson<T, APPROX_DEPTH>
 {
     fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
         sqrt_newton_raphson::<T, APPROX_DEPTH>(x)
     }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        sqrt_newton_raphson_try::<T, APPROX_DEPTH>(x)
+    }
+}
+
+/// Newton-Raphson sqrt iterated to `recommended_sqrt_depth`, so it cannot be
+/// under-iterated for `T`.
+pub struct SqrtV2<T: FixedPrecision> {
+    _precision: PhantomData<T>,
+}
+
+impl<T: FixedPrecision> SqrtV2<T> {
+    pub const APPROX_DEPTH: u32 = recommended_sqrt_depth::<T>();
+
+    pub fn new() -> Self {
+        Self {
+            _precision: PhantomData,
+        }
+    }
+}
+
+impl<T: FixedPrecision> Default for SqrtV2<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FixedPrecision> Function<T> for SqrtV2<T> {
+    fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
+        newton_sqrt(x, Self::APPROX_DEPTH, false).expect("sqrt computation failed")
+    }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        newton_sqrt(x, Self::APPROX_DEPTH, false)
+    }
 }
 
 pub struct SqrtLinearInterpLookupTable<T: FixedPrecision, const APPROX_DEPTH: u32> {
@@ -45,42 +87,90 @@ impl<T: FixedPrecision, const APPROX_DEPTH: u32> SqrtLinearInterpLookupTable<T,
             ),
         }
     }
+
+    /// Builds the table from `builder`, whose extrapolation and interpolation
+    /// modes `evaluate` then follows.
+    pub fn from_builder(builder: LookupTableBuilder<T>) -> Result<Self> {
+        Ok(Self {
+            lookup: builder.build(sqrt_newton_raphson::<T, APPROX_DEPTH>)?,
+        })
+    }
+
+    /// The interpolated value with the slope of its table segment; see
+    /// `LookupTable::evaluate_with_slope`.
+    pub fn evaluate_with_slope(
+        &self,
+        x: FixedDecimal<T>,
+    ) -> Result<(FixedDecimal<T>, FixedDecimal<T>)> {
+        self.lookup.evaluate_with_slope(x)
+    }
 }
 
 impl<T: FixedPrecision, const APPROX_DEPTH: u32> Function<T>
     for SqrtLinearInterpLookupTable<T, APPROX_DEPTH>
 {
     fn evaluate(&self, x: FixedDecimal<T>) -> FixedDecimal<T> {
-        let index = self.lookup.get_index(x).expect("Index not found");
-        if index + 1 >= self.lookup.table.len() {
-            return self.lookup.table[index];
-        }
-        let lower_value = self.lookup.step_size() * index + self.lookup.start();
-        linear_interpolation(
-            x,
-            lower_value,
-            lower_value + self.lookup.step_size(),
-            self.lookup.table[index],
-            self.lookup.table[index + 1],
-        )
+        self.lookup.try_evaluate(x).expect("Index not found")
+    }
+
+    fn evaluate_checked(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
+        self.lookup.try_evaluate(x)
     }
 }
 
+/// Square root over any magnitude: inputs between the table's first and
+/// last samples are interpolated, everything else goes to Newton-Raphson
+/// at `APPROX_DEPTH`. Out-of-table inputs never panic; only negative ones
+/// fail, as with every sqrt here.
+pub type SqrtHybrid<T, const APPROX_DEPTH: u32 = 20> =
+    Hybrid<T, SqrtNewtonRaphson<T, APPROX_DEPTH>>;
+
+/// Newton-Raphson square root. The iteration is seeded with a power of two
+/// taken from the bit length of the input, which is within a factor of two
+/// of the root, so convergence is quadratic from the first step and a depth
+/// of around 6 reaches full accuracy even at F18.
 pub fn sqrt_newton_raphson<T: FixedPrecision, const APPROX_DEPTH: u32>(
     x: FixedDecimal<T>,
 ) -> FixedDecimal<T> {
-    if x == 0 {
-        return FixedDecimal::<T>::from_i128(0);
-    }
-    let mut y = x / 2_i64;
-    for _ in 0..APPROX_DEPTH {
-        y = (y + x.div(y)) / 2_i64;
-    }
-    y
+    sqrt_newton_raphson_try::<T, APPROX_DEPTH>(x).expect("sqrt computation failed")
 }
 
 pub fn sqrt_newton_raphson_try<T: FixedPrecision, const APPROX_DEPTH: u32>(
     x: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    newton_sqrt(x, APPROX_DEPTH, false)
+}
+
+/// Like `sqrt_newton_raphson_try`, but stops as soon as an iteration no
+/// longer changes the estimate. `MAX_DEPTH` bounds the work for inputs whose
+/// iterates oscillate by one ulp. Prefer the fixed-depth version when
+/// deterministic latency matters.
+pub fn sqrt_newton_raphson_converge<T: FixedPrecision, const MAX_DEPTH: u32>(
+    x: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    newton_sqrt(x, MAX_DEPTH, true)
+}
+
+/// Newton depth that reaches full precision for any positive `T` input.
+/// The seed is within a factor of two of the root, and every step roughly
+/// doubles the number of correct bits, so the depth only has to cover the
+/// bit length of the largest raw root plus one step for truncation.
+pub const fn recommended_sqrt_depth<T: FixedPrecision>() -> u32 {
+    let scale_bits = i128::BITS - FixedDecimal::<T>::scale().leading_zeros();
+    let root_bits = (i128::BITS - 1 + scale_bits).div_ceil(2);
+    let mut correct_bits = 0;
+    let mut depth = 1;
+    while correct_bits < root_bits {
+        correct_bits = 2 * correct_bits + 1;
+        depth += 1;
+    }
+    depth
+}
+
+fn newton_sqrt<T: FixedPrecision>(
+    x: FixedDecimal<T>,
+    depth: u32,
+    stop_when_converged: bool,
 ) -> Result<FixedDecimal<T>> {
     if x < FixedDecimal::<T>::zero() {
         return Err(FixedFastError::DomainError(
@@ -90,42 +180,23 @@ pub fn sqrt_newton_raphson_try<T: FixedPrecision, const APPROX_DEPTH: u32>(
     if x == FixedDecimal::<T>::zero() {
         return Ok(FixedDecimal::<T>::zero());
     }
-    let mut y = x / 2_i64;
-    for _ in 0..APPROX_DEPTH {
-        y = (y + x.div(y)) / 2_i64;
-    }
-    Ok(y)
-}
-
-// TryFunction implementation for direct sqrt algorithm
-impl<T: FixedPrecision, const APPROX_DEPTH: u32> TryFunction<T>
-    for SqrtNewtonRaphson<T, APPROX_DEPTH>
-{
-    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
-        sqrt_newton_raphson_try::<T, APPROX_DEPTH>(x)
-    }
-}
-
-// TryFunction implementation for lookup table based sqrt
-impl<T: FixedPrecision, const APPROX_DEPTH: u32> TryFunction<T>
-    for SqrtLinearInterpLookupTable<T, APPROX_DEPTH>
-{
-    fn try_evaluate(&self, x: FixedDecimal<T>) -> Result<FixedDecimal<T>> {
-        let index = self.lookup.get_index(x)?;
-        if index + 1 >= self.lookup.table.len() {
-            return Ok(self.lookup.table[index]);
+    // the raw root is sqrt(raw * scale), which has half as many bits
+    let raw = x.to_raw();
+    let scale = FixedDecimal::<T>::scale();
+    let bits = x.raw_bits() + FixedDecimal::<T>::one().raw_bits();
+    let mut y = 1i128 << (bits / 2);
+    for _ in 0..depth {
+        let quotient = mul_div(raw, scale, y).ok_or(FixedFastError::Overflow)?;
+        let next = (y + quotient) / 2;
+        if stop_when_converged && next == y {
+            break;
         }
-        let lower_value = self.lookup.step_size() * index + self.lookup.start();
-        Ok(linear_interpolation(
-            x,
-            lower_value,
-            lower_value + self.lookup.step_size(),
-            self.lookup.table[index],
-            self.lookup.table[index + 1],
-        ))
+        y = next;
     }
+    Ok(FixedDecimal::from_raw(y))
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -136,6 +207,44 @@ mod tests {
         const PRECISION: u32 = 18;
     }
 
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F14;
+
+    impl FixedPrecision for F14 {
+        const PRECISION: u32 = 14;
+    }
+
+    fn assert_recommended_depth_converged<T: FixedPrecision>() {
+        let sqrt = SqrtV2::<T>::new();
+        let mut inputs = ["2", "0.25", "1.3453453453453453", "123456.789", "1000000"]
+            .map(|input| FixedDecimal::<T>::from_str(input).unwrap())
+            .to_vec();
+        inputs.push(FixedDecimal::min_positive());
+        inputs.push(FixedDecimal::from_raw(i128::MAX));
+        for x in inputs {
+            assert_eq!(
+                sqrt.evaluate(x),
+                newton_sqrt(x, SqrtV2::<T>::APPROX_DEPTH + 20, false).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_recommended_sqrt_depth() {
+        assert_eq!(recommended_sqrt_depth::<F9>(), 8);
+        assert_eq!(recommended_sqrt_depth::<F18>(), 8);
+        assert_recommended_depth_converged::<F9>();
+        assert_recommended_depth_converged::<F14>();
+        assert_recommended_depth_converged::<F18>();
+    }
+
     #[test]
     fn test_sqrt_newton_raphson() {
         let input = FixedDecimal::<F18>::from_str("1.3453453453453453").unwrap();
@@ -158,4 +267,98 @@ mod tests {
             FixedDecimal::<F18>::from_str("5.218632399692833084").unwrap()
         );
     }
+
+    #[test]
+    fn test_sqrt_hybrid() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let sqrt = SqrtHybrid::<F18, 12>::new(d("0"), d("40"), d("0.001"));
+        let table = SqrtLinearInterpLookupTable::<F18, 12>::new(d("0"), d("40"), d("0.001"));
+
+        let input = d("27.234124123124");
+        assert!(sqrt.in_table(input));
+        assert_eq!(sqrt.evaluate(input), table.evaluate(input));
+
+        for input in [d("39.9995"), d("40"), d("1234.5678"), d("1000000000000")] {
+            assert!(!sqrt.in_table(input));
+            assert_eq!(sqrt.evaluate(input), sqrt_newton_raphson::<F18, 12>(input));
+        }
+        assert_eq!(sqrt.evaluate(d("1000000")), d("1000"));
+        assert!(matches!(
+            Function::evaluate_checked(&sqrt, d("-1")),
+            Err(FixedFastError::DomainError(_))
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_clamped() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let (start, end) = (d("0"), d("16"));
+        let sqrt = SqrtLinearInterpLookupTable::<F18, 12>::new(start, end, d("0.001"));
+        assert!(Function::evaluate_checked(&sqrt, d("25")).is_err());
+        assert_eq!(
+            sqrt.evaluate_clamped(d("25"), start, end),
+            sqrt.evaluate(end)
+        );
+        // `end` holds the last sample, at 15.999
+        assert!((sqrt.evaluate_clamped(d("25"), start, end) - d("4")).abs() < d("0.001"));
+        assert_eq!(
+            sqrt.evaluate_clamped(d("-3"), start, end),
+            FixedDecimal::zero()
+        );
+        assert_eq!(
+            sqrt.evaluate_clamped(d("2.25"), start, end),
+            sqrt.evaluate(d("2.25"))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "min <= max")]
+    fn test_evaluate_clamped_reversed_bounds() {
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let sqrt = SqrtNewtonRaphson::<F18, 12>::new();
+        sqrt.evaluate_clamped(d("2"), d("16"), d("0"));
+    }
+
+    #[test]
+    fn test_sqrt_converges_at_depth_6() {
+        assert_eq!(
+            sqrt_newton_raphson::<F18, 6>(FixedDecimal::<F18>::from_i128(
+                1_000_000_000_000_000_000
+            )),
+            FixedDecimal::<F18>::from_i128(1_000_000_000)
+        );
+        assert_eq!(
+            sqrt_newton_raphson::<F18, 6>(FixedDecimal::<F18>::from_i128(2)),
+            FixedDecimal::<F18>::from_str("1.414213562373095048").unwrap()
+        );
+        assert_eq!(
+            sqrt_newton_raphson::<F18, 6>(FixedDecimal::<F18>::from_str("0.0004").unwrap()),
+            FixedDecimal::<F18>::from_str("0.02").unwrap()
+        );
+        assert_eq!(
+            sqrt_newton_raphson::<F18, 6>(FixedDecimal::<F18>::min_positive()),
+            FixedDecimal::<F18>::from_str("0.000000001").unwrap()
+        );
+        assert!(sqrt_newton_raphson_try::<F18, 6>(FixedDecimal::<F18>::from_i128(-1)).is_err());
+    }
+
+    #[test]
+    fn test_sqrt_converge_matches_full_depth() {
+        for input in [
+            "2",
+            "0.25",
+            "1.3453453453453453",
+            "123456.789",
+            "0.000000000000000001",
+        ] {
+            let x = FixedDecimal::<F18>::from_str(input).unwrap();
+            assert_eq!(
+                sqrt_newton_raphson_converge::<F18, 12>(x).unwrap(),
+                sqrt_newton_raphson::<F18, 12>(x)
+            );
+        }
+        assert!(
+            sqrt_newton_raphson_converge::<F18, 12>(FixedDecimal::<F18>::from_i128(-4)).is_err()
+        );
+    }
 }
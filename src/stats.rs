@@ -0,0 +1,532 @@
+use crate::{
+    error::{FixedFastError, Result},
+    exp::range_reduce_taylor_exp,
+    fixed_decimal::{FixedDecimal, FixedPrecision},
+    ln::range_reduce_arctanh_ln_try,
+    sqrt::sqrt_newton_raphson_try,
+    wide::I256,
+};
+
+pub fn mean<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<FixedDecimal<T>> {
+    non_empty(xs)?;
+    let mut sum = I256::ZERO;
+    for x in xs {
+        sum = sum
+            .checked_add(I256::from_i128(x.to_raw()))
+            .ok_or(FixedFastError::Overflow)?;
+    }
+    let raw = sum
+        .checked_div_i128(xs.len() as i128)
+        .ok_or(FixedFastError::Overflow)?;
+    Ok(FixedDecimal::from_raw(raw))
+}
+
+/// Population variance. Squared deviations are accumulated in a 256-bit
+/// intermediate so large deviations don't overflow before the final divide.
+pub fn variance<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<FixedDecimal<T>> {
+    non_empty(xs)?;
+    sum_of_squares_over(xs, xs.len() as i128)
+}
+
+/// Sample variance, dividing by `n - 1`. Needs at least two values.
+pub fn sample_variance<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<FixedDecimal<T>> {
+    if xs.len() < 2 {
+        return Err(FixedFastError::DomainError(
+            "sample variance needs at least two values",
+        ));
+    }
+    sum_of_squares_over(xs, xs.len() as i128 - 1)
+}
+
+pub fn std_dev<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<FixedDecimal<T>> {
+    sqrt_newton_raphson_try::<T, 40>(variance(xs)?)
+}
+
+pub fn sample_std_dev<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<FixedDecimal<T>> {
+    sqrt_newton_raphson_try::<T, 40>(sample_variance(xs)?)
+}
+
+/// Nth root of the product, computed as `exp(mean(ln(x)))` so the product
+/// itself is never formed.
+pub fn geometric_mean<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<FixedDecimal<T>> {
+    non_empty(xs)?;
+    all_positive(xs, "geometric mean is undefined for non-positive values")?;
+    let logs = xs
+        .iter()
+        .map(|x| range_reduce_arctanh_ln_try::<T, 20>(*x))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(range_reduce_taylor_exp::<T, 20>(mean(&logs)?))
+}
+
+/// Reciprocal of the mean of reciprocals.
+pub fn harmonic_mean<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<FixedDecimal<T>> {
+    non_empty(xs)?;
+    all_positive(xs, "harmonic mean is undefined for non-positive values")?;
+    let mut reciprocal_sum = FixedDecimal::<T>::zero();
+    for x in xs {
+        reciprocal_sum = reciprocal_sum.checked_add(FixedDecimal::one().checked_div(*x)?)?;
+    }
+    FixedDecimal::<T>::from_i128(xs.len() as i128).checked_div(reciprocal_sum)
+}
+
+/// `Σ w_i x_i / Σ w_i`. Both sums are accumulated in 256 bits, so the
+/// numerator cannot overflow before the final divide. Fails with a
+/// `DomainError` when the slices differ in length and `DivideByZero` when
+/// the weights sum to zero, which includes empty slices.
+pub fn weighted_mean<T: FixedPrecision>(
+    values: &[FixedDecimal<T>],
+    weights: &[FixedDecimal<T>],
+) -> Result<FixedDecimal<T>> {
+    if values.len() != weights.len() {
+        return Err(FixedFastError::DomainError(
+            "values and weights differ in length",
+        ));
+    }
+    let (mut weighted_sum, mut total_weight) = (I256::ZERO, I256::ZERO);
+    for (x, w) in values.iter().zip(weights) {
+        weighted_sum = weighted_sum
+            .checked_add(I256::mul(x.to_raw(), w.to_raw()))
+            .ok_or(FixedFastError::Overflow)?;
+        total_weight = total_weight
+            .checked_add(I256::from_i128(w.to_raw()))
+            .ok_or(FixedFastError::Overflow)?;
+    }
+    if total_weight == I256::ZERO {
+        return Err(FixedFastError::DivideByZero);
+    }
+    weighted_sum
+        .checked_div(total_weight)
+        .and_then(I256::to_i128)
+        .map(FixedDecimal::from_raw)
+        .ok_or(FixedFastError::Overflow)
+}
+
+/// The `p`-th quantile of `sorted`, interpolating linearly between the
+/// order statistics either side of rank `(n - 1) p` (Hyndman and Fan's
+/// type 7, the default in R and NumPy). `sorted` must already be in
+/// ascending order; this is not checked.
+pub fn quantile<T: FixedPrecision>(
+    sorted: &[FixedDecimal<T>],
+    p: FixedDecimal<T>,
+) -> Result<FixedDecimal<T>> {
+    non_empty(sorted)?;
+    if !(FixedDecimal::zero()..=FixedDecimal::one()).contains(&p) {
+        return Err(FixedFastError::DomainError("quantile must lie in [0, 1]"));
+    }
+    let rank = p
+        .to_raw()
+        .checked_mul(sorted.len() as i128 - 1)
+        .ok_or(FixedFastError::Overflow)?;
+    let scale = FixedDecimal::<T>::scale();
+    let index = (rank / scale) as usize;
+    let fraction = FixedDecimal::<T>::from_raw(rank % scale);
+    let lower = sorted[index];
+    match sorted.get(index + 1) {
+        Some(upper) if fraction != 0 => {
+            lower.checked_add(upper.checked_sub(lower)?.checked_mul(fraction)?)
+        }
+        _ => Ok(lower),
+    }
+}
+
+/// Running totals: element `i` is the sum of `xs[..=i]`.
+pub fn cumulative_sum<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Vec<FixedDecimal<T>> {
+    xs.iter()
+        .scan(FixedDecimal::zero(), |total, x| {
+            *total += *x;
+            Some(*total)
+        })
+        .collect()
+}
+
+/// Running products: element `i` is the product of `xs[..=i]`.
+pub fn cumulative_product<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Vec<FixedDecimal<T>> {
+    xs.iter()
+        .scan(FixedDecimal::one(), |total, x| {
+            *total *= *x;
+            Some(*total)
+        })
+        .collect()
+}
+
+/// Checked `cumulative_sum`, failing with `Overflow` on the first overflowing step.
+pub fn try_cumulative_sum<T: FixedPrecision>(
+    xs: &[FixedDecimal<T>],
+) -> Result<Vec<FixedDecimal<T>>> {
+    let mut total = FixedDecimal::zero();
+    xs.iter()
+        .map(|x| {
+            total = total.checked_add(*x)?;
+            Ok(total)
+        })
+        .collect()
+}
+
+/// Checked `cumulative_product`, failing with `Overflow` on the first overflowing step.
+pub fn try_cumulative_product<T: FixedPrecision>(
+    xs: &[FixedDecimal<T>],
+) -> Result<Vec<FixedDecimal<T>>> {
+    let mut total = FixedDecimal::one();
+    xs.iter()
+        .map(|x| {
+            total = total.checked_mul(*x)?;
+            Ok(total)
+        })
+        .collect()
+}
+
+/// Online mean and variance over a stream, using Welford's update so no
+/// samples are stored and no raw sum of squares is formed. The running sum
+/// and the sum of squared deviations are kept in 256 bits, and the mean is
+/// re-derived from the exact sum after each push, so truncation does not
+/// drift as the stream grows.
+#[derive(Debug, Clone, Copy)]
+pub struct RunningStats<T: FixedPrecision> {
+    count: u64,
+    sum: I256,
+    mean: FixedDecimal<T>,
+    m2: I256,
+}
+
+impl<T: FixedPrecision> RunningStats<T> {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            sum: I256::ZERO,
+            mean: FixedDecimal::zero(),
+            m2: I256::ZERO,
+        }
+    }
+
+    pub fn push(&mut self, x: FixedDecimal<T>) -> Result<()> {
+        let count = self.count + 1;
+        let sum = self
+            .sum
+            .checked_add(I256::from_i128(x.to_raw()))
+            .ok_or(FixedFastError::Overflow)?;
+        let mean = sum
+            .checked_div_i128(count as i128)
+            .ok_or(FixedFastError::Overflow)?;
+        let before = x.checked_sub(self.mean)?.to_raw();
+        let after = x
+            .to_raw()
+            .checked_sub(mean)
+            .ok_or(FixedFastError::Overflow)?;
+        self.m2 = self
+            .m2
+            .checked_add(I256::mul(before, after))
+            .ok_or(FixedFastError::Overflow)?;
+        self.count = count;
+        self.sum = sum;
+        self.mean = FixedDecimal::from_raw(mean);
+        Ok(())
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Result<FixedDecimal<T>> {
+        if self.count == 0 {
+            return Err(FixedFastError::DomainError(
+                "statistics are undefined for an empty stream",
+            ));
+        }
+        Ok(self.mean)
+    }
+
+    /// Population variance of the values pushed so far.
+    pub fn variance(&self) -> Result<FixedDecimal<T>> {
+        self.mean()?;
+        self.m2_over(self.count as i128)
+    }
+
+    /// Sample variance, dividing by `n - 1`. Needs at least two values.
+    pub fn sample_variance(&self) -> Result<FixedDecimal<T>> {
+        if self.count < 2 {
+            return Err(FixedFastError::DomainError(
+                "sample variance needs at least two values",
+            ));
+        }
+        self.m2_over(self.count as i128 - 1)
+    }
+
+    pub fn std_dev(&self) -> Result<FixedDecimal<T>> {
+        sqrt_newton_raphson_try::<T, 40>(self.variance()?)
+    }
+
+    fn m2_over(&self, divisor: i128) -> Result<FixedDecimal<T>> {
+        let scaled_divisor = FixedDecimal::<T>::scale()
+            .checked_mul(divisor)
+            .ok_or(FixedFastError::Overflow)?;
+        let raw = self
+            .m2
+            .checked_div_i128(scaled_divisor)
+            .ok_or(FixedFastError::Overflow)?;
+        Ok(FixedDecimal::from_raw(raw.max(0)))
+    }
+}
+
+impl<T: FixedPrecision> Default for RunningStats<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn all_positive<T: FixedPrecision>(xs: &[FixedDecimal<T>], message: &'static str) -> Result<()> {
+    if xs.iter().any(|x| *x <= 0) {
+        return Err(FixedFastError::DomainError(message));
+    }
+    Ok(())
+}
+
+fn non_empty<T: FixedPrecision>(xs: &[FixedDecimal<T>]) -> Result<()> {
+    if xs.is_empty() {
+        return Err(FixedFastError::DomainError(
+            "statistics are undefined for an empty slice",
+        ));
+    }
+    Ok(())
+}
+
+fn sum_of_squares_over<T: FixedPrecision>(
+    xs: &[FixedDecimal<T>],
+    divisor: i128,
+) -> Result<FixedDecimal<T>> {
+    let mean = mean(xs)?;
+    let mut sum = I256::ZERO;
+    for x in xs {
+        let deviation = x.checked_sub(mean)?.to_raw();
+        sum = sum
+            .checked_add(I256::mul(deviation, deviation))
+            .ok_or(FixedFastError::Overflow)?;
+    }
+    let scaled_divisor = FixedDecimal::<T>::scale()
+        .checked_mul(divisor)
+        .ok_or(FixedFastError::Overflow)?;
+    let raw = sum
+        .checked_div_i128(scaled_divisor)
+        .ok_or(FixedFastError::Overflow)?;
+    Ok(FixedDecimal::from_raw(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    fn dataset() -> Vec<FixedDecimal<F9>> {
+        [2, 4, 4, 4, 5, 5, 7, 9]
+            .iter()
+            .map(|x| FixedDecimal::<F9>::from_i128(*x))
+            .collect()
+    }
+
+    #[test]
+    fn test_mean_variance_std_dev() {
+        let xs = dataset();
+        assert_eq!(mean(&xs).unwrap(), 5);
+        assert_eq!(variance(&xs).unwrap(), 4);
+        assert_eq!(std_dev(&xs).unwrap(), 2);
+        assert_eq!(
+            sample_variance(&xs).unwrap(),
+            FixedDecimal::<F9>::from_str("4.571428571").unwrap()
+        );
+        assert_eq!(
+            sample_std_dev(&xs).unwrap(),
+            FixedDecimal::<F9>::from_str("2.138089935").unwrap()
+        );
+        assert!(mean::<F9>(&[]).is_err());
+        assert!(variance::<F9>(&[]).is_err());
+        assert!(sample_variance(&xs[..1]).is_err());
+    }
+
+    #[test]
+    fn test_weighted_mean() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let values = [d("10"), d("20"), d("30.5")];
+        let weights = [d("0.2"), d("0.5"), d("0.3")];
+        // 2 + 10 + 9.15
+        assert_eq!(weighted_mean(&values, &weights).unwrap(), d("21.15"));
+        // the weights need not sum to one: (3 * 2 + 1 * 6) / 4
+        assert_eq!(
+            weighted_mean(&[d("2"), d("6")], &[d("3"), d("1")]).unwrap(),
+            d("3")
+        );
+        assert_eq!(
+            weighted_mean(&[d("1"), d("2")], &[d("1"), d("2")]).unwrap(),
+            d("1.666666666")
+        );
+        assert!(matches!(
+            weighted_mean(&values, &weights[..2]),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(matches!(
+            weighted_mean(&[d("1"), d("2")], &[d("1"), d("-1")]),
+            Err(FixedFastError::DivideByZero)
+        ));
+        assert!(matches!(
+            weighted_mean::<F9>(&[], &[]),
+            Err(FixedFastError::DivideByZero)
+        ));
+    }
+
+    #[test]
+    fn test_weighted_mean_large_values() {
+        // each raw product exceeds 1e53, far beyond i128
+        let d = |s| FixedDecimal::<F18>::from_str(s).unwrap();
+        let values = [d("1000000000000"), d("3000000000000")];
+        let weights = [d("250000"), d("750000")];
+        assert_eq!(
+            weighted_mean(&values, &weights).unwrap(),
+            d("2500000000000")
+        );
+    }
+
+    #[test]
+    fn test_quantile() {
+        let d = |s| FixedDecimal::<F9>::from_str(s).unwrap();
+        let xs = dataset();
+        assert_eq!(quantile(&xs, d("0.5")).unwrap(), d("4.5"));
+        assert_eq!(quantile(&xs, d("0")).unwrap(), d("2"));
+        assert_eq!(quantile(&xs, d("1")).unwrap(), d("9"));
+        assert_eq!(quantile(&xs, d("0.25")).unwrap(), d("4"));
+        assert_eq!(quantile(&xs, d("0.9")).unwrap(), d("7.6"));
+        let odd = [d("-3"), d("1.5"), d("10")];
+        assert_eq!(quantile(&odd, d("0.5")).unwrap(), d("1.5"));
+        assert_eq!(quantile(&odd, d("0.1")).unwrap(), d("-2.1"));
+        assert_eq!(quantile(&[d("7")], d("0.3")).unwrap(), d("7"));
+        assert!(matches!(
+            quantile(&xs, d("1.01")),
+            Err(FixedFastError::DomainError(_))
+        ));
+        assert!(quantile(&xs, d("-0.5")).is_err());
+        assert!(quantile::<F9>(&[], d("0.5")).is_err());
+    }
+
+    #[test]
+    fn test_running_stats_matches_batch() {
+        let xs: Vec<FixedDecimal<F9>> = ["1.5", "-2.25", "3.125", "0.001", "100", "-7.75", "42.42"]
+            .iter()
+            .map(|x| FixedDecimal::from_str(x).unwrap())
+            .collect();
+        let mut stats = RunningStats::new();
+        assert!(stats.mean().is_err());
+        assert!(stats.variance().is_err());
+        for (i, x) in xs.iter().enumerate() {
+            stats.push(*x).unwrap();
+            let seen = &xs[..=i];
+            assert_eq!(stats.count(), seen.len() as u64);
+            assert_eq!(stats.mean().unwrap(), mean(seen).unwrap());
+            assert!(
+                stats
+                    .variance()
+                    .unwrap()
+                    .ulp_distance(variance(seen).unwrap())
+                    <= 2
+            );
+        }
+        assert!(
+            stats
+                .sample_variance()
+                .unwrap()
+                .ulp_distance(sample_variance(&xs).unwrap())
+                <= 2
+        );
+        assert!(stats.std_dev().unwrap().ulp_distance(std_dev(&xs).unwrap()) <= 2);
+
+        let mut stats = RunningStats::new();
+        for x in dataset() {
+            stats.push(x).unwrap();
+        }
+        assert_eq!(stats.mean().unwrap(), FixedDecimal::from_i128(5));
+        assert_eq!(stats.variance().unwrap(), FixedDecimal::from_i128(4));
+        assert_eq!(stats.std_dev().unwrap(), FixedDecimal::from_i128(2));
+    }
+
+    #[test]
+    fn test_running_stats_long_stream() {
+        // the raw sum of squares of this stream overflows i128 after a few
+        // hundred pushes
+        let big = FixedDecimal::<F18>::from_i128(1_000_000_000);
+        let mut stats = RunningStats::new();
+        for i in 0..1000 {
+            stats.push(if i % 2 == 0 { big } else { -big }).unwrap();
+        }
+        assert_eq!(stats.mean().unwrap(), FixedDecimal::zero());
+        assert_eq!(stats.std_dev().unwrap(), big);
+    }
+
+    #[test]
+    fn test_variance_large_deviations() {
+        // squaring a raw deviation of 1e28 overflows i128, the variance does not
+        let xs = [
+            FixedDecimal::<F18>::from_i128(-10_000_000_000),
+            FixedDecimal::<F18>::from_i128(10_000_000_000),
+        ];
+        assert_eq!(mean(&xs).unwrap(), 0);
+        assert_eq!(variance(&xs).unwrap(), 100_000_000_000_000_000_000_i128);
+    }
+
+    #[test]
+    fn test_geometric_and_harmonic_mean() {
+        let xs: Vec<FixedDecimal<F9>> = [1, 2, 4, 8]
+            .iter()
+            .map(|x| FixedDecimal::from_i128(*x))
+            .collect();
+        assert!(
+            (geometric_mean(&xs).unwrap() - FixedDecimal::from_str("2.828427125").unwrap()).abs()
+                < FixedDecimal::from_str("0.0000001").unwrap()
+        );
+        assert_eq!(
+            harmonic_mean(&xs[..3]).unwrap(),
+            FixedDecimal::<F9>::from_str("1.714285714").unwrap()
+        );
+        let with_zero = [FixedDecimal::<F9>::one(), FixedDecimal::zero()];
+        assert!(geometric_mean(&with_zero).is_err());
+        assert!(harmonic_mean(&with_zero).is_err());
+        assert!(geometric_mean::<F9>(&[]).is_err());
+        assert!(harmonic_mean(&[FixedDecimal::<F9>::from_i128(-2)]).is_err());
+    }
+
+    #[test]
+    fn test_cumulative_sum_and_product() {
+        let xs: Vec<FixedDecimal<F9>> = ["1.5", "-2", "0.25", "4"]
+            .iter()
+            .map(|x| FixedDecimal::from_str(x).unwrap())
+            .collect();
+        let mut sum = FixedDecimal::zero();
+        let mut product = FixedDecimal::one();
+        let mut sums = Vec::new();
+        let mut products = Vec::new();
+        for x in &xs {
+            sum += *x;
+            product *= *x;
+            sums.push(sum);
+            products.push(product);
+        }
+        assert_eq!(cumulative_sum(&xs), sums);
+        assert_eq!(cumulative_product(&xs), products);
+        assert_eq!(try_cumulative_sum(&xs).unwrap(), sums);
+        assert_eq!(try_cumulative_product(&xs).unwrap(), products);
+        assert!(cumulative_sum::<F9>(&[]).is_empty());
+
+        let big = [FixedDecimal::<F9>::from_raw(i128::MAX), FixedDecimal::one()];
+        assert!(try_cumulative_sum(&big).is_err());
+        let big = [FixedDecimal::<F9>::from_i128(10_000_000_000_000_000); 3];
+        assert!(try_cumulative_product(&big).is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use proptest::prelude::*;
+
+use crate::fixed_decimal::{FixedDecimal, FixedPrecision};
+
+/// Strategy over every representable value, with the extremes and zero
+/// weighted in so edge cases show up in short runs.
+pub fn any_fixed<T: FixedPrecision>() -> impl Strategy<Value = FixedDecimal<T>> {
+    prop_oneof![
+        Just(FixedDecimal::<T>::zero()),
+        Just(FixedDecimal::<T>::from_raw(i128::MIN)),
+        Just(FixedDecimal::<T>::from_raw(i128::MAX)),
+        any::<i128>().prop_map(FixedDecimal::<T>::from_raw),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F9;
+
+    impl FixedPrecision for F9 {
+        const PRECISION: u32 = 9;
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct F18;
+
+    impl FixedPrecision for F18 {
+        const PRECISION: u32 = 18;
+    }
+
+    proptest! {
+        #[test]
+        fn test_string_roundtrip_f9(x in any_fixed::<F9>()) {
+            prop_assert_eq!(FixedDecimal::<F9>::from_str(&x.to_string()).unwrap(), x);
+        }
+
+        #[test]
+        fn test_string_roundtrip_f18(x in any_fixed::<F18>()) {
+            prop_assert_eq!(FixedDecimal::<F18>::from_str(&x.to_string()).unwrap(), x);
+        }
+    }
+}
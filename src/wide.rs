@@ -0,0 +1,424 @@
+// 256-bit helpers used where an intermediate product of two raw i128 values
+// would overflow before being scaled back down.
+
+use core::fmt;
+use std::cmp::Ordering;
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+/// Signed 256-bit integer in two's complement, stored as two 128-bit halves.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct I256 {
+    hi: u128,
+    lo: u128,
+}
+
+impl I256 {
+    pub(crate) const ZERO: Self = Self { hi: 0, lo: 0 };
+    pub(crate) const ONE: Self = Self { hi: 0, lo: 1 };
+
+    pub(crate) const fn from_i128(x: i128) -> Self {
+        Self {
+            hi: if x < 0 { u128::MAX } else { 0 },
+            lo: x as u128,
+        }
+    }
+
+    /// Exact product of two i128 values.
+    pub(crate) const fn mul(a: i128, b: i128) -> Self {
+        let (hi, lo) = mul_u128(a.unsigned_abs(), b.unsigned_abs());
+        let product = Self { hi, lo };
+        if (a < 0) != (b < 0) {
+            product.wrapping_neg()
+        } else {
+            product
+        }
+    }
+
+    pub(crate) const fn is_negative(self) -> bool {
+        (self.hi as i128) < 0
+    }
+
+    pub(crate) const fn checked_add(self, rhs: Self) -> Option<Self> {
+        let (lo, carry) = self.lo.overflowing_add(rhs.lo);
+        let hi = self.hi.wrapping_add(rhs.hi).wrapping_add(carry as u128);
+        let sum = Self { hi, lo };
+        if self.is_negative() == rhs.is_negative() && sum.is_negative() != self.is_negative() {
+            None
+        } else {
+            Some(sum)
+        }
+    }
+
+    pub(crate) const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let (lo, borrow) = self.lo.overflowing_sub(rhs.lo);
+        let hi = self.hi.wrapping_sub(rhs.hi).wrapping_sub(borrow as u128);
+        let diff = Self { hi, lo };
+        if self.is_negative() != rhs.is_negative() && diff.is_negative() != self.is_negative() {
+            None
+        } else {
+            Some(diff)
+        }
+    }
+
+    pub(crate) const fn checked_neg(self) -> Option<Self> {
+        Self::ZERO.checked_sub(self)
+    }
+
+    /// Narrows to an i128, returning `None` when the value does not fit.
+    pub(crate) const fn to_i128(self) -> Option<i128> {
+        let narrowed = Self::from_i128(self.lo as i128);
+        if narrowed.hi == self.hi {
+            Some(self.lo as i128)
+        } else {
+            None
+        }
+    }
+
+    /// `10^exp`, or `None` once it no longer fits.
+    pub(crate) const fn checked_pow10(exp: u32) -> Option<Self> {
+        let mut power = Self::ONE;
+        let mut i = 0;
+        while i < exp {
+            let (carry, lo) = mul_u128(power.lo, 10);
+            let hi = match power.hi.checked_mul(10) {
+                Some(hi) => hi.checked_add(carry),
+                None => None,
+            };
+            power = match hi {
+                Some(hi) if (hi as i128) >= 0 => Self { hi, lo },
+                _ => return None,
+            };
+            i += 1;
+        }
+        Some(power)
+    }
+
+    /// `2^exp` for `exp < 255`.
+    pub(crate) const fn pow2(exp: u32) -> Self {
+        if exp < 128 {
+            Self {
+                hi: 0,
+                lo: 1 << exp,
+            }
+        } else {
+            Self {
+                hi: 1 << (exp - 128),
+                lo: 0,
+            }
+        }
+    }
+
+    /// Number of significant bits in the absolute value.
+    pub(crate) const fn bits(self) -> u32 {
+        let (hi, lo) = self.unsigned_abs();
+        if hi != 0 {
+            256 - hi.leading_zeros()
+        } else {
+            128 - lo.leading_zeros()
+        }
+    }
+
+    pub(crate) fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let [w0, w1, w2, w3] = mul_u256(self.unsigned_abs(), rhs.unsigned_abs());
+        if w2 != 0 || w3 != 0 {
+            return None;
+        }
+        Self::from_magnitude(self.is_negative() != rhs.is_negative(), (w1, w0))
+    }
+
+    /// Computes `self * b / d` with a 512-bit intermediate, truncating toward
+    /// zero. Returns `None` when `d` is zero or the quotient does not fit.
+    pub(crate) fn checked_mul_div(self, b: Self, d: Self) -> Option<Self> {
+        let product = mul_u256(self.unsigned_abs(), b.unsigned_abs());
+        let quotient = div_u512_by_u256(product, d.unsigned_abs())?;
+        let negative = (self.is_negative() != b.is_negative()) != d.is_negative();
+        Self::from_magnitude(negative, quotient)
+    }
+
+    pub(crate) fn checked_div(self, d: Self) -> Option<Self> {
+        self.checked_mul_div(Self::ONE, d)
+    }
+
+    // applies a sign to an unsigned magnitude, failing if the result is out of range
+    const fn from_magnitude(negative: bool, (hi, lo): (u128, u128)) -> Option<Self> {
+        let magnitude = Self { hi, lo };
+        if !negative {
+            if magnitude.is_negative() {
+                None
+            } else {
+                Some(magnitude)
+            }
+        } else if hi > 1 << 127 || (hi == 1 << 127 && lo != 0) {
+            None
+        } else {
+            Some(magnitude.wrapping_neg())
+        }
+    }
+
+    const fn wrapping_neg(self) -> Self {
+        let (lo, carry) = (!self.lo).overflowing_add(1);
+        Self {
+            hi: (!self.hi).wrapping_add(carry as u128),
+            lo,
+        }
+    }
+
+    const fn unsigned_abs(self) -> (u128, u128) {
+        if self.is_negative() {
+            let abs = self.wrapping_neg();
+            (abs.hi, abs.lo)
+        } else {
+            (self.hi, self.lo)
+        }
+    }
+
+    /// Divides by `d`, truncating toward zero. Returns `None` when `d` is zero
+    /// or the quotient does not fit in an i128.
+    pub(crate) const fn checked_div_i128(self, d: i128) -> Option<i128> {
+        if d == 0 {
+            return None;
+        }
+        let (hi, lo) = self.unsigned_abs();
+        let quotient = match div_u256_by_u128(hi, lo, d.unsigned_abs()) {
+            Some(q) => q,
+            None => return None,
+        };
+        if self.is_negative() != (d < 0) {
+            if quotient > i128::MIN.unsigned_abs() {
+                None
+            } else {
+                Some((quotient as i128).wrapping_neg())
+            }
+        } else if quotient > i128::MAX as u128 {
+            None
+        } else {
+            Some(quotient as i128)
+        }
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.hi as i128, self.lo).cmp(&(other.hi as i128, other.lo))
+    }
+}
+
+impl fmt::Display for I256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut magnitude = self.unsigned_abs();
+        let mut digits = Vec::new();
+        loop {
+            let (quotient, digit) = div_rem_u256_by_u128(magnitude, 10);
+            digits.push(b'0' + digit as u8);
+            magnitude = quotient;
+            if magnitude == (0, 0) {
+                break;
+            }
+        }
+        if self.is_negative() {
+            digits.push(b'-');
+        }
+        digits.reverse();
+        f.write_str(std::str::from_utf8(&digits).expect("digits are ascii"))
+    }
+}
+
+/// Computes `a * b / d` with a 256-bit intermediate, truncating toward zero.
+pub(crate) const fn mul_div(a: i128, b: i128, d: i128) -> Option<i128> {
+    match a.checked_mul(b) {
+        Some(product) => product.checked_div(d),
+        None => I256::mul(a, b).checked_div_i128(d),
+    }
+}
+
+const fn mul_u128(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & LOW_MASK);
+    let (b1, b0) = (b >> 64, b & LOW_MASK);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    let mid = (p00 >> 64) + (p01 & LOW_MASK) + (p10 & LOW_MASK);
+    let lo = (p00 & LOW_MASK) | (mid << 64);
+    let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (hi, lo)
+}
+
+// Full 512-bit product of two unsigned 256-bit values, least significant limb first.
+fn mul_u256(a: (u128, u128), b: (u128, u128)) -> [u128; 4] {
+    let mut limbs = [0u128; 4];
+    for (i, x) in [a.1, a.0].into_iter().enumerate() {
+        for (j, y) in [b.1, b.0].into_iter().enumerate() {
+            let (hi, lo) = mul_u128(x, y);
+            add_at(&mut limbs, i + j, lo);
+            add_at(&mut limbs, i + j + 1, hi);
+        }
+    }
+    limbs
+}
+
+fn add_at(limbs: &mut [u128; 4], mut index: usize, mut value: u128) {
+    while value != 0 && index < limbs.len() {
+        let (sum, carry) = limbs[index].overflowing_add(value);
+        limbs[index] = sum;
+        value = carry as u128;
+        index += 1;
+    }
+}
+
+// Bitwise long division of a 512-bit numerator by a 256-bit divisor, in the
+// same shape as `div_u256_by_u128`.
+fn div_u512_by_u256(n: [u128; 4], d: (u128, u128)) -> Option<(u128, u128)> {
+    if (n[3], n[2]) >= d {
+        return None;
+    }
+    let mut remainder = (n[3], n[2]);
+    let mut quotient = (0u128, 0u128);
+    for i in (0..256).rev() {
+        let bit = if i >= 128 {
+            (n[1] >> (i - 128)) & 1
+        } else {
+            (n[0] >> i) & 1
+        };
+        let carry = remainder.0 >> 127;
+        remainder = (
+            (remainder.0 << 1) | (remainder.1 >> 127),
+            (remainder.1 << 1) | bit,
+        );
+        quotient = ((quotient.0 << 1) | (quotient.1 >> 127), quotient.1 << 1);
+        if carry == 1 || remainder >= d {
+            let (lo, borrow) = remainder.1.overflowing_sub(d.1);
+            remainder = (
+                remainder.0.wrapping_sub(d.0).wrapping_sub(borrow as u128),
+                lo,
+            );
+            quotient.1 |= 1;
+        }
+    }
+    Some(quotient)
+}
+
+const fn div_rem_u256_by_u128((hi, lo): (u128, u128), d: u128) -> ((u128, u128), u128) {
+    let (quotient, remainder) = long_div(hi % d, lo, d);
+    ((hi / d, quotient), remainder)
+}
+
+const fn div_u256_by_u128(hi: u128, lo: u128, d: u128) -> Option<u128> {
+    if hi >= d {
+        return None;
+    }
+    Some(long_div(hi, lo, d).0)
+}
+
+// Schoolbook long division of a 256-bit numerator by a 128-bit divisor,
+// returning the quotient and remainder. Requires `hi < d`.
+const fn long_div(hi: u128, lo: u128, d: u128) -> (u128, u128) {
+    let mut remainder = hi;
+    let mut quotient = 0u128;
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((lo >> i) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= d {
+            remainder = remainder.wrapping_sub(d);
+            quotient |= 1;
+        }
+    }
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(mul_div(6, 7, 3), Some(14));
+        assert_eq!(mul_div(-6, 7, 4), Some(-10));
+        assert_eq!(mul_div(-6, -7, -4), Some(-10));
+        // the product overflows an i128 but the quotient does not
+        let big = 10i128.pow(30);
+        assert_eq!(mul_div(big, big, big), Some(big));
+        assert_eq!(mul_div(i128::MAX, i128::MAX, i128::MAX), Some(i128::MAX));
+        assert_eq!(mul_div(i128::MIN, 1, 1), Some(i128::MIN));
+        assert_eq!(mul_div(i128::MIN, -1, 1), None);
+        assert_eq!(mul_div(big, big, 1), None);
+        assert_eq!(mul_div(1, 1, 0), None);
+    }
+
+    #[test]
+    fn test_wide_mul_div() {
+        let big = I256::checked_pow10(60).unwrap();
+        let e30 = I256::checked_pow10(30).unwrap();
+        assert_eq!(e30.checked_mul(e30), Some(big));
+        assert_eq!(big.checked_mul(e30), None);
+        // the 512-bit product 10^120 is divided back into range
+        assert_eq!(big.checked_mul_div(big, big), Some(big));
+        assert_eq!(
+            big.checked_mul_div(I256::from_i128(-7), I256::from_i128(2)),
+            big.checked_mul(I256::from_i128(-35))
+                .and_then(|x| x.checked_div(I256::from_i128(10)))
+        );
+        assert_eq!(big.checked_mul_div(big, I256::ONE), None);
+        assert_eq!(big.checked_div(I256::ZERO), None);
+        assert_eq!(
+            I256::from_i128(-7).checked_div(I256::from_i128(2)),
+            Some(I256::from_i128(-3))
+        );
+        assert_eq!(I256::checked_pow10(77), None);
+        assert_eq!(
+            I256::checked_pow10(38),
+            Some(I256::from_i128(10i128.pow(38)))
+        );
+        assert_eq!(
+            I256::checked_pow10(76),
+            I256::checked_pow10(38).and_then(|e38| e38.checked_mul(e38))
+        );
+    }
+
+    #[test]
+    fn test_display_and_narrowing() {
+        let e40 = I256::checked_pow10(40).unwrap();
+        assert_eq!(e40.to_string(), format!("1{}", "0".repeat(40)));
+        assert_eq!(
+            e40.checked_neg().unwrap().to_string(),
+            format!("-1{}", "0".repeat(40))
+        );
+        assert_eq!(
+            I256::from_i128(i128::MIN).to_string(),
+            i128::MIN.to_string()
+        );
+        assert_eq!(I256::ZERO.to_string(), "0");
+        assert_eq!(I256::from_i128(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!(e40.to_i128(), None);
+        assert!(e40.checked_neg().unwrap() < I256::ZERO);
+        assert_eq!(I256::pow2(200).bits(), 201);
+    }
+
+    #[test]
+    fn test_checked_add() {
+        let a = I256::mul(i128::MAX, i128::MAX);
+        let b = I256::mul(i128::MIN, i128::MAX);
+        assert_eq!(
+            a.checked_add(b).unwrap().checked_div_i128(i128::MAX),
+            Some(-1)
+        );
+        assert_eq!(
+            I256::from_i128(-5).checked_add(I256::from_i128(3)),
+            Some(I256::from_i128(-2))
+        );
+        assert_eq!(
+            I256::ZERO.checked_add(I256::from_i128(-1)),
+            Some(I256::from_i128(-1))
+        );
+    }
+}